@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::rules::{find_rule, Requirement, Rule};
+
 #[derive(Debug, PartialEq)]
 pub struct AST {
   pub name: String,
@@ -18,10 +20,17 @@ pub enum Kind {
   Variable(Variable),
   Reference(Reference),
   Type,
+  Conditional,
+  /// `friend` declaration (named by the befriended class or function)
+  Friend,
+  Macro(Macro),
   Unhandled(String),
   LintError(LintErrorTypes),
 }
 
+/// Name prefix of abstract interfaces unless the project configures another
+pub const DEFAULT_ABSTRACT_PREFIX: &str = "Abstract";
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Class {
   pub is_abstract: bool,
@@ -30,7 +39,9 @@ pub struct Class {
 #[derive(Debug, PartialEq, Clone)]
 pub struct Variable {
   pub is_const: bool,
+  pub storage: StorageClass,
   pub visibility: String,
+  pub declarator: Declarator,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -38,6 +49,36 @@ pub struct Function {
   pub is_virtual: bool,
   pub visibility: String,
   pub in_external_namespace: Option<String>,
+  pub returns: Declarator,
+  pub storage: StorageClass,
+  /// Declared with `= delete`
+  pub is_deleted: bool,
+  /// Declared with `= default`
+  pub is_defaulted: bool,
+}
+
+/// Preprocessor `#define` (its body is kept as references to the
+/// identifiers used in it)
+#[derive(Debug, PartialEq, Clone)]
+pub struct Macro {
+  pub is_function_like: bool,
+  pub value: Option<String>,
+}
+
+/// Storage class specifiers of a declaration
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct StorageClass {
+  pub is_static: bool,
+  pub is_extern: bool,
+}
+
+/// How a type is used in a declaration (`T`, `T&` or `T*`). `is_const`
+/// is set if the referenced type is const.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Declarator {
+  Value,
+  Reference { is_const: bool },
+  Pointer { is_const: bool },
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -47,6 +88,8 @@ pub enum  Reference {
   Write,
   Call,
   Depend,
+  /// Creating an instance of a class (`new`, `std::make_unique`, ...)
+  Instantiate,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -54,19 +97,62 @@ pub struct LintError {
   pub kind: LintErrorTypes,
   pub range: core::ops::Range<usize>,
   pub file_path: String,
+  pub secondary_labels: Vec<LintLabel>,
+  /// Additional explanations that do not point to a location
+  pub notes: Vec<String>,
+}
+
+/// Additional location (possibly in another file) that helps to
+/// understand a lint error
+#[derive(Debug, PartialEq, Clone)]
+pub struct LintLabel {
+  pub message: String,
+  pub range: core::ops::Range<usize>,
+  pub file_path: String,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum LintErrorTypes {
   InterfaceOnlyPublicMethods(String, String),
   InterfaceShouldNotDefineAttrs(String, String),
+  InterfaceNonPublicMethod(String, String, String),
+  InterfaceShouldNotDefineConstructor(String),
+  InterfaceNameMissingAbstractPrefix(String, String),
+  InterfaceNameOnConcreteClass(String, String, String),
+  InterfaceTypesDefinedInClass(String, String),
+  InterfaceConcreteTypeInSignature(String, String, String),
+  InterfaceStructHasMethod(String, String),
+  InterfaceStructNonPodMember(String, String),
+  InterfaceRevealsImplementation(String, String, String),
+  InterfaceFriendDeclaration(String, String),
   DerivedClassesAllAttrsPrivate(String, String),
+  DerivedClassesOnlyInterfacePublic(String, String),
+  DerivedClassesExtraMethodUsage(String, String),
+  DerivedClassesInstantiatedOutsideMain(String, String),
+  DerivedClassesConcreteDependency(String, String, String, Option<String>),
+  DerivedClassesFriendDeclaration(String, String),
   GlobalVariablesUsage(String),
   GlobalVariablesDeclaration(String),
+  GlobalVariablesStaticAttribute(String, String),
+  GlobalVariablesStaticLocal(String, String),
+  GlobalVariablesSingleton(String, String),
+  GlobalVariablesMacroConstant(String),
   DeriveFromAbstractInterface(String),
   AvoidInitMethods(String),
+  BitRotConditionalCompilation(String, String),
+  GeneralUtf8Encoding(usize),
+  GeneralSpacesInsteadOfTabs(usize),
+  GeneralMixedLineEndings(usize),
+  GeneralMissingFinalNewline,
+  AvoidUnusedGetterSetter(String, String),
+  AvoidGetterSetter(String, String),
+  DataStructuresReturnedByReference(String, String),
+  DataStructuresPassedByReference(String, String, String),
   ParserUnhandled(String),
   LintInstructionNotParseble(String),
+  LintInstructionUnused(String),
+  LintInstructionMissingReason(String),
+  LintInstructionExpired(String, String),
   // c++ specific errors without broader meaning
   // for other languages
   CppAbstractClassMissingDefaultDestructor(String),
@@ -76,6 +162,11 @@ pub enum LintErrorTypes {
   CppDerivedClassMethodHasVirtualEnding(String, String),
   CppDerivesAlwaysPublic(String),
   CppDerivesAlwaysFromAbstractInterfaces(String),
+  CppAbstractClassIncludesNonAbstractHeader(String, String),
+  CppAbstractClassHasImplementationFile(String),
+  CppUnnecessaryInclude(String),
+  CppIncludeOnlyNeededByImplementation(String, String),
+  CppMissingDirectInclude(String, String),
 }
 
 impl Display for LintErrorTypes {
@@ -89,12 +180,73 @@ impl Display for LintErrorTypes {
       InterfaceShouldNotDefineAttrs(class_name, attr_name) => {
         write!(f, "Abstract class `{class_name}` must not have attributes ('{attr_name}')")
       },
+      InterfaceNonPublicMethod(class_name, visibility, method_name) => {
+        write!(f, "Abstract class `{class_name}` must not define {visibility} methods ('{method_name}')")
+      },
+      InterfaceShouldNotDefineConstructor(class_name) => {
+        write!(f, "Abstract class `{class_name}` must not define a constructor")
+      },
+      InterfaceNameMissingAbstractPrefix(class_name, prefix) => {
+        write!(f, "Class '{class_name}' only declares pure virtual methods and is therefore an abstract class. Its name must follow the schema `{prefix}<name>` ('{prefix}{class_name}')")
+      },
+      InterfaceNameOnConcreteClass(class_name, members, prefix) => {
+        write!(f, "Class '{class_name}' is named like an abstract class but has concrete members ({members}). Either make it abstract or remove the `{prefix}` prefix")
+      },
+      InterfaceTypesDefinedInClass(class_name, type_name) => {
+        write!(f, "'{type_name}' belongs to the context of abstract class '{class_name}' and should be defined inside of the class instead of the global namespace")
+      },
+      InterfaceConcreteTypeInSignature(class_name, method_name, type_name) => {
+        write!(f, "Method '{method_name}' of abstract class '{class_name}' must not depend on concrete class '{type_name}'. Use its abstract interface instead.")
+      },
+      InterfaceStructHasMethod(struct_name, method_name) => {
+        write!(f, "Structure '{struct_name}' of an abstract interface must be plain old data and must not define method '{method_name}'")
+      },
+      InterfaceStructNonPodMember(struct_name, member_name) => {
+        write!(f, "Structure '{struct_name}' of an abstract interface must be plain old data, but member '{member_name}' is not")
+      },
+      InterfaceRevealsImplementation(class_name, name, term) => {
+        write!(f, "'{name}' of abstract class '{class_name}' reveals implementation details ('{term}'). Names of abstract interfaces should have a high-level view of the task.")
+      },
+      InterfaceFriendDeclaration(class_name, friend) => {
+        write!(f, "Abstract class '{class_name}' must not declare '{friend}' as friend")
+      },
       DerivedClassesAllAttrsPrivate(class_name, attr_name) => {
         write!(f, "Derived class '{class_name}' must not have non private attributes ('{attr_name}')")
       },
+      DerivedClassesOnlyInterfacePublic(class_name, method_name) => {
+        write!(f, "Derived class '{class_name}' should only make the methods of its abstract interface public ('{method_name}' is not part of the interface)")
+      },
+      DerivedClassesConcreteDependency(class_name, name, concrete, interface) => {
+        write!(f, "Class '{class_name}' depends on concrete class '{concrete}' ('{name}'). ")?;
+        match interface {
+          Some(interface) => write!(f, "Use its abstract interface '{interface}' instead."),
+          None => write!(f, "Use an abstract interface instead."),
+        }
+      },
+      DerivedClassesFriendDeclaration(class_name, friend) => {
+        write!(f, "Friend '{friend}' of class '{class_name}' gets access to its private attributes. Use the public interface instead.")
+      },
+      DerivedClassesInstantiatedOutsideMain(class_name, function_name) => {
+        write!(f, "Concrete class '{class_name}' should only be instantiated in 'main'/'setup' or a factory and not in '{function_name}'. Depend on its abstract interface instead.")
+      },
+      DerivedClassesExtraMethodUsage(class_name, method_name) => {
+        write!(f, "Method '{method_name}' is not part of the abstract interface of '{class_name}'. Use the abstract interface or extend it instead of depending on the concrete class")
+      },
       GlobalVariablesDeclaration(name) => {
         write!(f, "It's not allowed to create global variables ('{name}'). Global variables create invisible coupling.")
       },
+      GlobalVariablesStaticAttribute(class_name, attr_name) => {
+        write!(f, "Static attribute '{attr_name}' of class '{class_name}' is hidden global state. Global variables create invisible coupling.")
+      },
+      GlobalVariablesStaticLocal(function_name, var_name) => {
+        write!(f, "Static variable '{var_name}' in function '{function_name}' is hidden global state. Global variables create invisible coupling.")
+      },
+      GlobalVariablesSingleton(class_name, method_name) => {
+        write!(f, "Singleton access '{method_name}' of class '{class_name}' is hidden global state. Global variables create invisible coupling.")
+      },
+      GlobalVariablesMacroConstant(name) => {
+        write!(f, "Macro '{name}' should be replaced by a typed 'constexpr' constant")
+      },
       GlobalVariablesUsage(name) => {
         write!(f, "It's not allowed to use global variables ('{name}'). Global variables create invisible coupling.")
       },
@@ -104,6 +256,33 @@ impl Display for LintErrorTypes {
       AvoidInitMethods(name) => {
         write!(f, "Class '{name}' should not provide an init function. Initialisation should be done in constructor.")
       },
+      BitRotConditionalCompilation(scope, directive) => {
+        write!(f, "Conditional compilation '{directive}' should not be used inside of '{scope}'. Write platform agnostic code or switch whole files instead.")
+      },
+      GeneralUtf8Encoding(byte_offset) => {
+        write!(f, "Source files must be encoded in UTF-8 (invalid byte at offset {byte_offset})")
+      },
+      GeneralSpacesInsteadOfTabs(line) => {
+        write!(f, "Line {line} is indented with tabs. Spaces must be used for formatting instead of tabs.")
+      },
+      GeneralMixedLineEndings(line) => {
+        write!(f, "Line {line} uses a different line ending (CRLF/LF) than the rest of the file")
+      },
+      GeneralMissingFinalNewline => {
+        write!(f, "File should end with a newline")
+      },
+      AvoidUnusedGetterSetter(class_name, method_name) => {
+        write!(f, "Getter/setter '{method_name}' of class '{class_name}' is not used outside of the class. Only define getters and setters when there is a concrete need.")
+      },
+      AvoidGetterSetter(class_name, method_name) => {
+        write!(f, "Consider replacing getter/setter '{method_name}' of class '{class_name}' with a method that performs a specific action")
+      },
+      DataStructuresReturnedByReference(class_name, method_name) => {
+        write!(f, "Method '{method_name}' of class '{class_name}' must return data structures and attributes by value and not by reference or pointer")
+      },
+      DataStructuresPassedByReference(class_name, method_name, param_name) => {
+        write!(f, "Method '{method_name}' of class '{class_name}' must not take the data structure '{param_name}' by non-const reference")
+      },
       CppAbstractClassMissingDefaultDestructor(class_name) => {
         write!(f, "Abstract class '{class_name}' should provide a default destructor.")
       },
@@ -125,9 +304,33 @@ impl Display for LintErrorTypes {
       CppDerivesAlwaysFromAbstractInterfaces(class_name) => {
         write!(f, "Class '{class_name}': Derives must always be from abstract interfaces")
       },
+      CppAbstractClassIncludesNonAbstractHeader(class_name, header) => {
+        write!(f, "Abstract class '{class_name}' must only include headers of abstract classes or the standard library (not allowed {header})")
+      },
+      CppAbstractClassHasImplementationFile(class_name) => {
+        write!(f, "Abstract class '{class_name}' must consist only of a header file. It is not allowed to have a `.cpp` file.")
+      },
+      CppUnnecessaryInclude(header) => {
+        write!(f, "Header {header} is included but not needed")
+      },
+      CppIncludeOnlyNeededByImplementation(header, source) => {
+        write!(f, "Header {header} is only needed by the implementation and must be included in '{source}' instead")
+      },
+      CppMissingDirectInclude(symbol, header) => {
+        write!(f, "'{symbol}' is used without including \"{header}\" directly. Include it to make the dependency explicit")
+      },
       LintInstructionNotParseble(comment) => {
         write!(f, "could not parse lint instruction in comment: {comment}")
       },
+      LintInstructionUnused(ident) => {
+        write!(f, "Lint instruction '{ident}' does not suppress any finding and should be removed")
+      },
+      LintInstructionMissingReason(ident) => {
+        write!(f, "Lint instruction '{ident}' is missing a reason. Deviations from the guidelines must be justified.")
+      },
+      LintInstructionExpired(ident, until) => {
+        write!(f, "Lint instruction '{ident}' was only valid until {until}. Fix the suppressed findings or extend the deadline.")
+      },
       ParserUnhandled(message) => {
         write!(f, "{message}")
       },
@@ -135,10 +338,119 @@ impl Display for LintErrorTypes {
   }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Severity {
+  Error,
+  Warning,
+  Note,
+}
+
+impl LintErrorTypes {
+  /// MUST/MUST NOT rules are errors and SHOULD/SHOULD NOT rules warnings
+  pub fn severity(&self) -> Severity {
+    use LintErrorTypes::*;
+
+    match &self {
+      // a getter or setter without any user has no concrete need at all
+      AvoidUnusedGetterSetter(_, _) => Severity::Error,
+      ParserUnhandled(_) => Severity::Note,
+      LintInstructionUnused(_)|LintInstructionExpired(_, _) => Severity::Warning,
+      _ => match self.rule().map(|rule| rule.requirement) {
+        Some(Requirement::Should|Requirement::ShouldNot) => Severity::Warning,
+        Some(Requirement::Must|Requirement::MustNot)|None => Severity::Error,
+      },
+    }
+  }
+
+  /// Stable identifier that is used to suppress the finding with a
+  /// `lint: ignore <ID> <reason>` comment
+  pub fn id(&self) -> &'static str {
+    use LintErrorTypes::*;
+
+    match &self {
+      InterfaceOnlyPublicMethods(_, _) => "E_MODULES_ABSTRACT_CLASSES_ONLY_PUBLIC_METHODS",
+      InterfaceShouldNotDefineAttrs(_, _) => "E_MODULES_ABSTRACT_CLASSES_NO_ATTRS",
+      InterfaceNonPublicMethod(_, _, _) => "E_MODULES_ABSTRACT_CLASSES_NO_PRIVATE_METHODS",
+      InterfaceShouldNotDefineConstructor(_) => "E_MODULES_ABSTRACT_CLASSES_NO_CONSTRUCTOR",
+      InterfaceNameMissingAbstractPrefix(_, _) => "E_MODULES_ABSTRACT_CLASSES_NAME_PREFIX",
+      InterfaceNameOnConcreteClass(_, _, _) => "E_MODULES_ABSTRACT_CLASSES_PREFIX_ON_CONCRETE_CLASS",
+      InterfaceConcreteTypeInSignature(_, _, _) => "E_MODULES_ABSTRACT_CLASSES_CONCRETE_TYPE_IN_SIGNATURE",
+      InterfaceRevealsImplementation(_, _, _) => "E_MODULES_ABSTRACT_CLASSES_REVEALS_IMPLEMENTATION",
+      InterfaceFriendDeclaration(_, _) => "E_MODULES_ABSTRACT_CLASSES_FRIEND",
+      InterfaceTypesDefinedInClass(_, _) => "E_STRUCTS_ENUMS_DEFINED_IN_ABSTRACT_CLASSES",
+      InterfaceStructHasMethod(_, _) => "E_STRUCTS_ENUMS_STRUCT_HAS_METHOD",
+      InterfaceStructNonPodMember(_, _) => "E_STRUCTS_ENUMS_STRUCT_NON_POD_MEMBER",
+      // both findings belong to the same requirement but public attributes
+      // and public methods are suppressed separately
+      DerivedClassesAllAttrsPrivate(_, _) => "E_MODULES_DERIVED_CLASSES_ALL_ATTRS_PRIVATE",
+      DerivedClassesOnlyInterfacePublic(_, _) => "E_MODULES_DERIVED_CLASSES_ONLY_INTERFACE_PUBLIC",
+      DerivedClassesFriendDeclaration(_, _) => "E_MODULES_DERIVED_CLASSES_FRIEND",
+      DerivedClassesExtraMethodUsage(_, _) => "E_MODULES_DERIVE_FROM_ABSTRACT_CLASSES_EXTRA_METHOD_USAGE",
+      DerivedClassesInstantiatedOutsideMain(_, _) => "E_MODULES_DERIVE_FROM_ABSTRACT_CLASSES_INSTANTIATED_OUTSIDE_MAIN",
+      DerivedClassesConcreteDependency(_, _, _, _) => "E_MODULES_DERIVE_FROM_ABSTRACT_CLASSES_CONCRETE_DEPENDENCY",
+      DeriveFromAbstractInterface(_) => "E_MODULES_DERIVE_FROM_ABSTRACT_CLASSES",
+      AvoidInitMethods(_) => "E_MODULES_NO_INIT_FUNCTION",
+      AvoidGetterSetter(_, _) => "E_MODULES_AVOID_GETTER_SETTER",
+      AvoidUnusedGetterSetter(_, _) => "E_MODULES_AVOID_GETTER_SETTER_UNUSED",
+      DataStructuresReturnedByReference(_, _) => "E_DATA_STRUCTURES_RETURNED_BY_REFERENCE",
+      DataStructuresPassedByReference(_, _, _) => "E_DATA_STRUCTURES_PASSED_BY_REFERENCE",
+      GlobalVariablesUsage(_) => "E_GLOBAL_VARIABLES_USAGE",
+      GlobalVariablesDeclaration(_) => "E_GLOBAL_VARIABLES_DECLARATION",
+      GlobalVariablesStaticAttribute(_, _) => "E_GLOBAL_VARIABLES_STATIC_ATTRIBUTE",
+      GlobalVariablesStaticLocal(_, _) => "E_GLOBAL_VARIABLES_STATIC_LOCAL",
+      GlobalVariablesSingleton(_, _) => "E_GLOBAL_VARIABLES_SINGLETON",
+      GlobalVariablesMacroConstant(_) => "E_GLOBAL_VARIABLES_MACRO_CONSTANT",
+      BitRotConditionalCompilation(_, _) => "E_BIT_ROT_CONDITIONAL_COMPILATION",
+      GeneralUtf8Encoding(_) => "E_GENERAL_UTF8_ENCODING",
+      GeneralSpacesInsteadOfTabs(_) => "E_GENERAL_SPACES_INSTEAD_OF_TABS",
+      GeneralMixedLineEndings(_) => "E_GENERAL_MIXED_LINE_ENDINGS",
+      GeneralMissingFinalNewline => "E_GENERAL_MISSING_FINAL_NEWLINE",
+      ParserUnhandled(_) => "E_PARSER_UNHANDLED",
+      LintInstructionNotParseble(_) => "E_LINT_INSTRUCTION_NOT_PARSEABLE",
+      LintInstructionUnused(_) => "E_LINT_INSTRUCTION_UNUSED",
+      LintInstructionMissingReason(_) => "E_LINT_INSTRUCTION_MISSING_REASON",
+      LintInstructionExpired(_, _) => "E_LINT_INSTRUCTION_EXPIRED",
+      CppAbstractClassMissingDefaultDestructor(_) => "E_CPP_ABSTRACT_CLASS_MISSING_DEFAULT_DESTRUCTOR",
+      CppAbstractClassMethodNotVirtual(_, _) => "E_CPP_ABSTRACT_CLASS_METHOD_NOT_VIRTUAL",
+      CppAbstractClassMethodMissingVirtualEnding(_, _) => "E_CPP_ABSTRACT_CLASS_METHOD_MISSING_VIRTUAL_ENDING",
+      CppDerivedClassMethodIsVirtual(_, _) => "E_CPP_DERIVED_CLASS_METHOD_IS_VIRTUAL",
+      CppDerivedClassMethodHasVirtualEnding(_, _) => "E_CPP_DERIVED_CLASS_METHOD_HAS_VIRTUAL_ENDING",
+      CppDerivesAlwaysPublic(_) => "E_CPP_DERIVES_ALWAYS_PUBLIC",
+      CppDerivesAlwaysFromAbstractInterfaces(_) => "E_CPP_DERIVES_ALWAYS_FROM_ABSTRACT_INTERFACES",
+      CppAbstractClassIncludesNonAbstractHeader(_, _) => "E_CPP_ABSTRACT_CLASS_INCLUDES_NON_ABSTRACT_HEADER",
+      CppAbstractClassHasImplementationFile(_) => "E_CPP_ABSTRACT_CLASS_HAS_IMPLEMENTATION_FILE",
+      CppUnnecessaryInclude(_) => "E_CPP_UNNECESSARY_INCLUDE",
+      CppIncludeOnlyNeededByImplementation(_, _) => "E_CPP_INCLUDE_ONLY_NEEDED_BY_IMPLEMENTATION",
+      CppMissingDirectInclude(_, _) => "E_CPP_MISSING_DIRECT_INCLUDE",
+    }
+  }
+
+  /// Rule of the styleguide that is violated (`None` for findings about
+  /// the analysis itself)
+  pub fn rule(&self) -> Option<&'static Rule> {
+    find_rule(self.id())
+  }
+}
+
+/// `lint: ignore <ID> <reason>` comment that suppresses the findings with
+/// the given ID inside of `scope`
+#[derive(Debug, PartialEq, Clone)]
 pub struct LintInstruction {
   pub ident: String,
   pub reason: String,
+  /// Date (`YYYY-MM-DD`) after which the suppression expires
+  pub until: Option<String>,
+  /// Reference to the ticket that tracks the deviation
+  pub ticket: Option<String>,
+  /// Location of the comment
+  pub range: core::ops::Range<usize>,
+  pub scope: core::ops::Range<usize>,
+}
+
+impl LintInstruction {
+  pub fn has_reason(&self) -> bool {
+    !self.reason.trim_start_matches("reason:").trim().is_empty()
+  }
 }
 
 impl AST {
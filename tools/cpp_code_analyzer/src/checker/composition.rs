@@ -0,0 +1,107 @@
+use crate::ast::{AST, Kind, Declarator, LintError, LintErrorTypes, Reference};
+
+use super::{check_if_has_main_entrypoint, get_concrete_class, get_parameters, Config, InScope};
+
+/// Concrete classes are created in the composition root (the file with
+/// `main` or `setup`) or in dedicated factories and handed to the classes
+/// that need them. Everywhere else only abstract interfaces are used.
+pub fn check_concrete_instantiations(ast: &[AST], vars: &InScope, config: &Config) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  for file in ast.iter() {
+    if check_if_has_main_entrypoint(file, &config.entry_points) || is_factory_file(&file.name, config) {
+      continue;
+    }
+
+    for node in file.children.iter() {
+      let functions: Vec<&AST> = match &node.kind {
+        Kind::Function(_) => vec![node],
+        Kind::Class(_) => node.children.iter().filter(|child| matches!(child.kind, Kind::Function(_))).collect(),
+        _ => continue,
+      };
+      for function in functions {
+        errors.append(&mut check_instantiations_in_function(function, &file.name, vars));
+      }
+    }
+  }
+
+  errors
+}
+
+fn is_factory_file(file_path: &str, config: &Config) -> bool {
+  let file_path = file_path.replace('\\', "/");
+  config.factory_files.iter().any(|factory| {
+    file_path == *factory || file_path.ends_with(&format!("/{}", factory.trim_start_matches("./")))
+  })
+}
+
+fn check_instantiations_in_function(function: &AST, file_path: &str, vars: &InScope) -> Vec<LintError> {
+  let mut errors = vec![];
+  let param_count = get_parameters(function).count();
+
+  for child in function.children.iter().skip(param_count) {
+    let instantiated = match &child.kind {
+      Kind::Reference(Reference::Instantiate) => get_concrete_class(&child.name, vars),
+      Kind::Variable(var) if var.declarator == Declarator::Value => {
+        child.dependencies.iter().find_map(|dep| get_concrete_class(&dep.name, vars))
+      }
+      _ => None,
+    };
+    if let Some(class_name) = instantiated {
+      errors.push(LintError {
+        kind: LintErrorTypes::DerivedClassesInstantiatedOutsideMain(class_name.to_string(), function.name.trim().to_string()),
+        range: child.range.clone(),
+        file_path: file_path.to_string(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+  }
+
+  errors
+}
+
+/// Classes hold (and get passed) other parts of the system through their
+/// abstract interfaces only. Depending on the concrete class couples them
+/// to one specific implementation.
+pub fn check_concrete_dependencies(ast: &[AST], vars: &InScope) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  for file in ast.iter() {
+    for class in file.children.iter().filter(|node| matches!(node.kind, Kind::Class(cl) if !cl.is_abstract)) {
+      let mut dependencies = vec![];
+      for child in class.children.iter() {
+        match &child.kind {
+          Kind::Variable(_) => dependencies.push(child),
+          Kind::Function(_) => dependencies.extend(get_parameters(child)),
+          _ => (),
+        }
+      }
+
+      for dependency in dependencies {
+        let concrete = dependency.dependencies.iter()
+          .filter_map(|dep| get_concrete_class(&dep.name, vars))
+          .find(|concrete| *concrete != class.name);
+        if let Some(concrete) = concrete {
+          errors.push(LintError {
+            kind: LintErrorTypes::DerivedClassesConcreteDependency(
+              class.name.clone(), dependency.name.clone(), concrete.to_string(), get_abstract_interface(concrete, vars),
+            ),
+            range: dependency.range.clone(),
+            file_path: file.name.clone(),
+            secondary_labels: vec![],
+            notes: vec![],
+          });
+        }
+      }
+    }
+  }
+
+  errors
+}
+
+/// The (first) abstract base class that could replace a concrete class
+fn get_abstract_interface(class_name: &str, vars: &InScope) -> Option<String> {
+  let class = vars.classes.get(class_name)?;
+  class.bases.iter().find(|base| vars.classes.get(*base).is_some_and(|base| base.is_abstract)).cloned()
+}
@@ -0,0 +1,320 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{AST, Kind, Declarator, Function, LintError, LintErrorTypes, Reference};
+
+use super::{strip_template_arguments, Config, NON_POD_STANDARD_TYPES};
+
+/// Headers of abstract classes may only pull in other abstract headers
+/// or headers shipped with the compiler. Everything else (Arduino,
+/// external libraries, concrete classes) leaks implementation details
+/// into the interface.
+pub fn check_abstract_class_includes(ast: &[AST], config: &Config) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  for file in ast.iter() {
+    let abstract_class = file.children.iter().find(|node| match node.kind {
+      Kind::Class(cl) => cl.is_abstract,
+      _ => false,
+    });
+    let Some(abstract_class) = abstract_class else {
+      continue;
+    };
+
+    for include in file.dependencies.iter() {
+      if !is_abstract_or_standard_header(&include.name, ast, &config.abstract_prefix) {
+        errors.push(LintError {
+          kind: LintErrorTypes::CppAbstractClassIncludesNonAbstractHeader(abstract_class.name.clone(), include.name.clone()),
+          range: include.range.clone(),
+          file_path: file.name.clone(),
+          secondary_labels: vec![],
+          notes: vec![],
+        });
+      }
+    }
+  }
+
+  errors
+}
+
+fn is_abstract_or_standard_header(include: &str, ast: &[AST], prefix: &str) -> bool {
+  if let Some(header) = include.strip_prefix('<').and_then(|header| header.strip_suffix('>')) {
+    return STANDARD_HEADERS.contains(&header);
+  }
+
+  let header = include.trim_matches('"');
+  if STANDARD_HEADERS.contains(&header) {
+    return true;
+  }
+
+  match find_included_file(header, ast) {
+    Some(file) => defines_only_abstract_classes_or_data_structures(file, ast),
+    // headers outside of the project can only be judged by their name
+    None => file_name(header).starts_with(prefix),
+  }
+}
+
+fn find_included_file<'a>(header: &str, ast: &'a [AST]) -> Option<&'a AST> {
+  let header = header.trim_start_matches("./").trim_start_matches("../");
+  let suffix = format!("/{header}");
+
+  ast.iter().find(|file| match file.kind {
+    Kind::File { content: _ } => file.name == header || file.name.ends_with(&suffix),
+    _ => false,
+  })
+}
+
+/// Structures and enums shared by several abstract classes live in a
+/// header of their own, which is part of the interfaces as long as it only
+/// contains plain old data.
+fn defines_only_abstract_classes_or_data_structures(file: &AST, ast: &[AST]) -> bool {
+  let mut has_interface = false;
+
+  for node in file.children.iter() {
+    match node.kind {
+      Kind::Class(cl) if !cl.is_abstract => return false,
+      Kind::Type if !is_plain_old_data(node, ast) => return false,
+      Kind::Class(_)|Kind::Type => has_interface = true,
+      _ => (),
+    }
+  }
+
+  has_interface
+}
+
+fn is_plain_old_data(structure: &AST, ast: &[AST]) -> bool {
+  structure.children.iter().all(|member| match &member.kind {
+    Kind::Function(_) => false,
+    Kind::Variable(var) if var.declarator == Declarator::Value => member.dependencies.iter().all(|dep| {
+      let type_name = strip_template_arguments(&dep.name);
+      !NON_POD_STANDARD_TYPES.contains(&type_name) && !is_project_class(type_name.rsplit("::").next().unwrap_or(type_name), ast)
+    }),
+    _ => true,
+  })
+}
+
+fn is_project_class(name: &str, ast: &[AST]) -> bool {
+  ast.iter().any(|file| file.children.iter().any(|node| matches!(node.kind, Kind::Class(_)) && node.name == name))
+}
+
+/// Headers provided by the compiler (C++ standard library and the C
+/// compatibility headers)
+const STANDARD_HEADERS: &[&str] = &[
+  "algorithm", "any", "array", "atomic", "barrier", "bit", "bitset",
+  "cassert", "cctype", "cerrno", "cfenv", "cfloat", "charconv", "chrono",
+  "cinttypes", "climits", "clocale", "cmath", "codecvt", "compare",
+  "complex", "concepts", "condition_variable", "coroutine", "csetjmp",
+  "csignal", "cstdarg", "cstddef", "cstdint", "cstdio", "cstdlib",
+  "cstring", "ctime", "cuchar", "cwchar", "cwctype", "deque",
+  "exception", "execution", "expected", "filesystem", "format",
+  "forward_list", "fstream", "functional", "future", "initializer_list",
+  "iomanip", "ios", "iosfwd", "iostream", "istream", "iterator", "latch",
+  "limits", "list", "locale", "map", "memory", "memory_resource", "mutex",
+  "new", "numbers", "numeric", "optional", "ostream", "queue", "random",
+  "ranges", "ratio", "regex", "scoped_allocator", "semaphore", "set",
+  "shared_mutex", "source_location", "span", "sstream", "stack",
+  "stdexcept", "stop_token", "streambuf", "string", "string_view",
+  "system_error", "thread", "tuple", "type_traits", "typeindex",
+  "typeinfo", "unordered_map", "unordered_set", "utility", "valarray",
+  "variant", "vector", "version",
+  "assert.h", "ctype.h", "errno.h", "fenv.h", "float.h", "inttypes.h",
+  "limits.h", "locale.h", "math.h", "setjmp.h", "signal.h", "stdarg.h",
+  "stdbool.h", "stddef.h", "stdint.h", "stdio.h", "stdlib.h", "string.h",
+  "time.h", "uchar.h", "wchar.h", "wctype.h",
+];
+
+/// Headers must only include what they need to compile. Includes that
+/// are only needed by the implementation belong into the `.cpp` file and
+/// symbols of other project headers should be included directly instead
+/// of relying on transitive includes.
+pub fn check_necessary_includes(ast: &[AST], config: &Config) -> Vec<LintError> {
+  let mut errors = vec![];
+  let providers = get_symbol_providers(ast);
+
+  for file in ast.iter() {
+    if !matches!(file.kind, Kind::File { content: _ }) {
+      continue;
+    }
+
+    let used = get_used_symbols(file);
+    let defined = get_provided_symbols(file);
+    let interface_header = find_sibling_file(file, ast, &[".h", ".hpp"]).filter(|_| !is_header(&file.name));
+    let implementation = find_sibling_file(file, ast, &[".cpp"]).filter(|_| is_header(&file.name));
+    let is_abstract_header = file.children.iter().any(|node| matches!(node.kind, Kind::Class(cl) if cl.is_abstract));
+    let mut directly_included = HashSet::new();
+
+    for include in file.dependencies.iter() {
+      let Some(header) = find_included_file(include.name.trim_matches('"'), ast) else {
+        continue;
+      };
+      directly_included.insert(header.name.clone());
+      if interface_header.is_some_and(|interface| interface.name == header.name) {
+        continue;
+      }
+      // already reported by `check_abstract_class_includes`
+      if is_abstract_header && !is_abstract_or_standard_header(&include.name, ast, &config.abstract_prefix) {
+        continue;
+      }
+
+      let provided = get_provided_symbols(header);
+      if uses_any(&used, &provided) {
+        continue;
+      }
+
+      let kind = match implementation {
+        Some(source) if uses_any(&get_used_symbols(source), &provided) => {
+          LintErrorTypes::CppIncludeOnlyNeededByImplementation(include.name.clone(), source.name.clone())
+        }
+        _ => LintErrorTypes::CppUnnecessaryInclude(include.name.clone()),
+      };
+      errors.push(LintError {
+        kind,
+        range: include.range.clone(),
+        file_path: file.name.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+
+    // the implementation may rely on everything its own header includes
+    if let Some(interface) = interface_header {
+      directly_included.insert(interface.name.clone());
+      for include in interface.dependencies.iter() {
+        if let Some(header) = find_included_file(include.name.trim_matches('"'), ast) {
+          directly_included.insert(header.name.clone());
+        }
+      }
+    }
+
+    let mut reported = HashSet::new();
+    for (symbol, range) in used.iter() {
+      let Some(Some(header)) = providers.get(symbol) else {
+        continue;
+      };
+      if header == &file.name || defined.contains(symbol) || directly_included.contains(header) {
+        continue;
+      }
+      if reported.insert(header.clone()) {
+        errors.push(LintError {
+          kind: LintErrorTypes::CppMissingDirectInclude(symbol.clone(), file_name(header).to_string()),
+          range: range.clone(),
+          file_path: file.name.clone(),
+          secondary_labels: vec![],
+          notes: vec![],
+        });
+      }
+    }
+  }
+
+  errors
+}
+
+fn uses_any(used: &[(String, core::ops::Range<usize>)], provided: &HashSet<String>) -> bool {
+  used.iter().any(|(symbol, _)| provided.contains(symbol))
+}
+
+/// Maps each symbol to the project header defining it. Symbols defined
+/// in more than one header are ambiguous and map to `None`.
+fn get_symbol_providers(ast: &[AST]) -> HashMap<String, Option<String>> {
+  let mut providers = HashMap::default();
+
+  for file in ast.iter().filter(|file| is_header(&file.name)) {
+    for symbol in get_provided_symbols(file) {
+      providers.entry(symbol)
+        .and_modify(|provider| *provider = None)
+        .or_insert_with(|| Some(file.name.clone()));
+    }
+  }
+
+  providers
+}
+
+fn get_provided_symbols(file: &AST) -> HashSet<String> {
+  file.children.iter().filter_map(|node| match &node.kind {
+    Kind::Class(_)|Kind::Type|Kind::Variable(_)|Kind::Macro(_) => Some(node.name.trim().to_string()),
+    Kind::Function(Function { in_external_namespace: None, .. }) => Some(node.name.trim().to_string()),
+    _ => None,
+  }).filter(|name| !name.is_empty()).collect()
+}
+
+/// All symbols referenced in a file together with the location of the
+/// reference
+fn get_used_symbols(file: &AST) -> Vec<(String, core::ops::Range<usize>)> {
+  let mut used = vec![];
+
+  for node in file.children.iter() {
+    collect_used_symbols(node, &file.range, &mut used);
+  }
+
+  used
+}
+
+fn collect_used_symbols(node: &AST, parent_range: &core::ops::Range<usize>, used: &mut Vec<(String, core::ops::Range<usize>)>) {
+  let range = if node.range.is_empty() { parent_range.clone() } else { node.range.clone() };
+
+  match &node.kind {
+    // a method called on an object is no symbol of its own
+    Kind::Reference(Reference::Call) if !node.dependencies.is_empty() => (),
+    Kind::Reference(_) => {
+      for symbol in get_leading_identifiers(&node.name) {
+        used.push((symbol.to_string(), range.clone()));
+      }
+    }
+    Kind::Function(Function { in_external_namespace: Some(namespace), .. }) => {
+      used.push((namespace.clone(), range.clone()));
+    }
+    // identifiers passed as arguments are variables without a declared type
+    Kind::Variable(_) if node.dependencies.is_empty() => {
+      for symbol in get_leading_identifiers(&node.name) {
+        used.push((symbol.to_string(), range.clone()));
+      }
+    }
+    _ => (),
+  }
+
+  for child in node.children.iter().chain(node.dependencies.iter()) {
+    collect_used_symbols(child, &range, used);
+  }
+}
+
+/// Identifiers of a reference that are not qualified by a namespace,
+/// class or object (`hal` and `Led` of `hal::Button<Led>`). Qualified
+/// names are provided by the header of their qualifier.
+fn get_leading_identifiers(name: &str) -> Vec<&str> {
+  let mut identifiers = vec![];
+  let mut start = None;
+
+  for (idx, c) in name.char_indices().chain([(name.len(), ' ')]) {
+    if c.is_alphanumeric() || c == '_' {
+      start.get_or_insert(idx);
+      continue;
+    }
+    let Some(begin) = start.take() else {
+      continue;
+    };
+    let before = name[..begin].trim_end();
+    let is_qualified = before.ends_with("::") || before.ends_with('.') || before.ends_with("->");
+    if !is_qualified && !name[begin..idx].starts_with(|c: char| c.is_ascii_digit()) {
+      identifiers.push(&name[begin..idx]);
+    }
+  }
+
+  identifiers
+}
+
+/// File with the same path but a different extension (e.g. the `.cpp`
+/// file of a header)
+fn find_sibling_file<'a>(file: &AST, ast: &'a [AST], extensions: &[&str]) -> Option<&'a AST> {
+  let (stem, _extension) = file.name.rsplit_once('.')?;
+
+  ast.iter().find(|other| {
+    extensions.iter().any(|extension| other.name == format!("{stem}{extension}"))
+  })
+}
+
+fn is_header(path: &str) -> bool {
+  path.ends_with(".h") || path.ends_with(".hpp")
+}
+
+fn file_name(path: &str) -> &str {
+  path.rsplit('/').next().unwrap_or(path)
+}
@@ -0,0 +1,1450 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::ast::{AST, Kind, Declarator, Function, LintError, LintErrorTypes, LintLabel, Macro, Reference, Severity, DEFAULT_ABSTRACT_PREFIX};
+
+mod composition;
+use composition::{check_concrete_dependencies, check_concrete_instantiations};
+mod includes;
+use includes::{check_abstract_class_includes, check_necessary_includes};
+mod naming;
+use naming::check_forbidden_terms;
+mod suppressions;
+use suppressions::{apply_lint_instructions, get_lint_instructions};
+pub use suppressions::{current_date, report_lint_instructions};
+mod text;
+use text::check_source_text;
+mod usage;
+use usage::{check_concrete_method_calls, check_getter_setter_usage, is_trivial_accessor};
+
+/// Terms that reveal the implementation of an abstract interface
+/// (bus protocols, peripherals and chips)
+pub const DEFAULT_FORBIDDEN_TERMS: [&str; 17] = [
+  "i2c", "spi", "uart", "usart", "modbus", "gpio", "rs232", "rs485", "canbus",
+  "onewire", "pwm", "adc", "esp32", "esp8266", "stm32", "avr", "arduino",
+];
+
+/// Project specific settings of the checks
+pub struct Config {
+  pub forbidden_terms: Vec<String>,
+  /// Files (besides the one with `main`/`setup`) that may instantiate
+  /// concrete classes
+  pub factory_files: Vec<String>,
+  /// Date (`YYYY-MM-DD`) that decides whether lint instructions with an
+  /// `until=` field are expired. Without a date no instruction expires.
+  pub today: Option<String>,
+  /// Name prefix that marks a class as abstract interface
+  pub abstract_prefix: String,
+  /// Functions that mark the composition root in addition to `main` and
+  /// `setup`/`loop`
+  pub entry_points: Vec<String>,
+  /// Settings for the files below a directory. Settings of more specific
+  /// directories win.
+  pub paths: Vec<PathConfig>,
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Self {
+      forbidden_terms: DEFAULT_FORBIDDEN_TERMS.iter().map(|term| term.to_string()).collect(),
+      factory_files: vec![],
+      today: None,
+      abstract_prefix: DEFAULT_ABSTRACT_PREFIX.to_string(),
+      entry_points: vec![],
+      paths: vec![],
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RuleSetting {
+  Off,
+  /// Enabled with the severity of its requirement level
+  On,
+  Severity(Severity),
+}
+
+#[derive(Debug, Default)]
+pub struct PathConfig {
+  /// Files below this directory are affected (in the form of the file
+  /// paths given to the checker)
+  pub directory: PathBuf,
+  /// `directory` relative to the configuration file, which is the base of
+  /// the globs
+  pub glob_base: PathBuf,
+  /// Depth of the directory of the configuration file in the file system.
+  /// Rule settings of deeper configuration files win.
+  pub depth: usize,
+  pub include: Vec<glob::Pattern>,
+  pub exclude: Vec<glob::Pattern>,
+  pub rules: HashMap<String, RuleSetting>,
+}
+
+impl PathConfig {
+  fn relative_path(&self, file_path: &str) -> Option<PathBuf> {
+    Path::new(file_path).strip_prefix(&self.directory).ok().map(|path| self.glob_base.join(path))
+  }
+}
+
+impl Config {
+  /// Files have to be included (if a path config has include globs) and
+  /// must not be excluded by any path config
+  pub fn is_included(&self, file_path: &str) -> bool {
+    self.paths.iter().all(|path| match path.relative_path(file_path) {
+      Some(relative) => {
+        let included = path.include.is_empty() || path.include.iter().any(|glob| glob.matches_path(&relative));
+        included && !path.exclude.iter().any(|glob| glob.matches_path(&relative))
+      }
+      None => true,
+    })
+  }
+
+  /// Severity of the error after applying the rule settings (`None` if the
+  /// rule is disabled)
+  pub fn get_severity(&self, error: &LintError) -> Option<Severity> {
+    let mut paths: Vec<&PathConfig> = self.paths.iter()
+      .filter(|path| path.relative_path(&error.file_path).is_some())
+      .collect();
+    paths.sort_by_key(|path| path.depth);
+
+    let setting = paths.iter().rev().find_map(|path| path.rules.get(error.kind.id()));
+    match setting {
+      Some(RuleSetting::Off) => None,
+      Some(RuleSetting::Severity(severity)) => Some(*severity),
+      Some(RuleSetting::On)|None => Some(error.kind.severity()),
+    }
+  }
+}
+
+pub fn check_global_codechunk(ast: Vec<AST>) -> Vec<LintError> {
+  check_global_codechunk_with_config(ast, &Config::default())
+}
+
+pub fn check_global_codechunk_with_config(ast: Vec<AST>, config: &Config) -> Vec<LintError> {
+  let ast = mark_abstract_classes(ast, &config.abstract_prefix);
+  let vars = get_scope(&ast);
+  let source = TextFile {
+    content: "".to_string(),
+    file_path: "".to_string(),
+  };
+  let mut project_errors = check_abstract_class_includes(&ast, config);
+  project_errors.append(&mut check_abstract_class_implementation_files(&ast, config));
+  project_errors.append(&mut check_types_defined_in_abstract_classes(&ast));
+  project_errors.append(&mut check_structs_in_abstract_headers(&ast, &vars));
+  project_errors.append(&mut check_forbidden_terms(&ast, config));
+  project_errors.append(&mut check_macros(&ast, config));
+  project_errors.append(&mut check_concrete_instantiations(&ast, &vars, config));
+  project_errors.append(&mut check_concrete_dependencies(&ast, &vars));
+  project_errors.append(&mut check_necessary_includes(&ast, config));
+  project_errors.append(&mut check_concrete_method_calls(&ast, &vars));
+  project_errors.append(&mut check_getter_setter_usage(&ast, &vars, &config.abstract_prefix));
+  project_errors.append(&mut check_source_text(&ast));
+  let globals = get_globals_of_main_files(&ast, config);
+  let instructions = get_lint_instructions(&ast);
+  let ast = add_lint_errors_to_codechunk(ast, &vars, config);
+  let mut errors = error_message_from_global_codechunk(ast, &source, &vars);
+  errors.append(&mut project_errors);
+  label_global_variable_definitions(&mut errors, &globals);
+  let errors = apply_lint_instructions(errors, &instructions, config.today.as_deref());
+  errors.into_iter().filter(|error| config.get_severity(error).is_some()).collect()
+}
+
+/// The parser only knows the default prefix of abstract classes. Marks the
+/// classes with the configured prefix as abstract before checks, graphs or
+/// fixes look at them.
+pub fn mark_abstract_classes(ast: Vec<AST>, prefix: &str) -> Vec<AST> {
+  ast.into_iter().map(|mut file| {
+    for node in file.children.iter_mut() {
+      if let Kind::Class(cl) = &mut node.kind {
+        cl.is_abstract = node.name.starts_with(prefix);
+      }
+    }
+    file
+  }).collect()
+}
+
+fn error_message_from_global_codechunk(ast: Vec<AST>, code: &TextFile, vars: &InScope) -> Vec<LintError> {
+  let mut errors = vec![];
+  for node in ast.into_iter() {
+    errors.append(&mut error_message_from_ast(node, code, &vars));
+  }
+
+  errors
+}
+
+pub fn add_lint_errors(ast: Vec<AST>) -> Vec<AST> {
+  let vars = get_scope(&ast);
+  let config = Config::default();
+
+  ast.into_iter().map(|mut node| {
+    match &node.kind {
+      Kind::File { content } => {
+        let source = TextFile {
+          content: content.clone(),
+          file_path: node.name.clone(),
+        };
+        let has_main_entrypoint = check_if_has_main_entrypoint(&node, &config.entry_points);
+        node.children = node.children.into_iter().map(|node| {
+          add_lint_errors_for_node(node, &source, &vars, has_main_entrypoint, &config)
+        }).collect();
+      },
+      _ => todo!("{:?}", node.kind),
+    }
+    node
+  }).collect()
+}
+
+fn add_lint_errors_to_codechunk(ast: Vec<AST>, vars: &InScope, config: &Config) -> Vec<AST> {
+  ast.into_iter().map(|mut node| {
+    match &node.kind {
+      Kind::File { content } => {
+        let source = TextFile {
+          content: content.clone(),
+          file_path: node.name.clone(),
+        };
+        let has_main_entrypoint = check_if_has_main_entrypoint(&node, &config.entry_points);
+        node.children = node.children.into_iter().map(|node| {
+          add_lint_errors_for_node(node, &source, &vars, has_main_entrypoint, config)
+        }).collect();
+      },
+      _ => (),
+    }
+    node
+  }).collect()
+}
+
+pub fn filter_references_in_scope(ast: Vec<AST>) -> Vec<AST> {
+  let vars = get_scope(&ast);
+
+  ast.into_iter().map(|mut node| {
+    match &node.kind {
+      Kind::File { content: _ } => {
+        node.children = node.children.into_iter().map(|mut node| {
+          match node.kind.clone() {
+            Kind::Function(fun) => {
+              match &fun.in_external_namespace {
+                None => filter_references_in_function(node, |name| { vars.constants.contains(name)  }),
+                Some(namespace) => filter_references_in_function(node, |name| {
+                  let empty = HashSet::default();
+                  let class_vars = vars.namespaces.get(namespace).unwrap_or(&empty);
+                  class_vars.contains(name) || vars.constants.contains(name)
+                }),
+              }
+            },
+            Kind::Class(_) => {
+              let class_name = &node.name;
+              node.children = node.children.into_iter().map(|node| {
+                match node.kind.clone() {
+                  Kind::Function(_) => {
+                    filter_references_in_function(node, |name| {
+                      let empty = HashSet::default();
+                      let class_vars = vars.namespaces.get(class_name).unwrap_or(&empty);
+                      class_vars.contains(name) || vars.constants.contains(name)
+                    })
+                  },
+                  _ => node,
+                }
+              }).collect();
+              node
+            },
+            _ => node,
+          }
+        }).collect();
+      },
+      _ => (),
+    }
+    node
+  }).collect()
+}
+
+fn get_scope(ast: &Vec<AST>) -> InScope {
+  let vars = get_variables_from_all_classes(ast);
+  let constants = get_constants(ast);
+  let classes = get_classes_from_all_files(ast);
+  let types = get_types_from_all_files(ast);
+  InScope {
+    constants,
+    namespaces: vars,
+    classes,
+    types,
+  }
+}
+
+fn check_abstract_class(node: &AST, class_name: &str, code: &TextFile, vars: &InScope, prefix: &str) -> Vec<LintError> {
+  let mut errors = vec![];
+  let mut has_default_destructor = false;
+
+  for child in node.children.iter() {
+    match &child.kind {
+      Kind::Variable(vl) => {
+        if vl.visibility != "public" {
+          errors.push(LintError {
+            kind: LintErrorTypes::InterfaceOnlyPublicMethods(class_name.to_string(), vl.visibility.clone()),
+            range: child.range.clone(),
+            file_path: code.file_path.clone(),
+            secondary_labels: vec![],
+            notes: vec![],
+          });
+        }
+        if !vl.is_const {
+          errors.push(LintError {
+            kind: LintErrorTypes::InterfaceShouldNotDefineAttrs(class_name.to_string(), child.name.clone()),
+            range: child.range.clone(),
+            file_path: code.file_path.clone(),
+            secondary_labels: vec![],
+            notes: vec![],
+          });
+        }
+      }
+      Kind::Function(fun) => {
+        if fun.is_virtual && child.name == format!("~{class_name}") {
+          has_default_destructor = true;
+        }
+        if is_special_member_without_behaviour(child, fun, class_name) {
+          continue;
+        }
+        if child.name == class_name {
+          errors.push(LintError {
+            kind: LintErrorTypes::InterfaceShouldNotDefineConstructor(class_name.to_string()),
+            range: child.range.clone(),
+            file_path: code.file_path.clone(),
+            secondary_labels: vec![],
+            notes: vec![],
+          });
+          continue;
+        }
+        if fun.visibility != "public" {
+          errors.push(LintError {
+            kind: LintErrorTypes::InterfaceNonPublicMethod(class_name.to_string(), fun.visibility.clone(), child.name.clone()),
+            range: child.range.clone(),
+            file_path: code.file_path.clone(),
+            secondary_labels: vec![],
+            notes: vec![],
+          });
+        }
+        errors.append(&mut check_function_is_virtual(&child, &fun, class_name, code));
+        errors.append(&mut check_data_structures_by_value(child, fun, class_name, code, vars));
+        errors.append(&mut check_abstract_signature_types(child, class_name, code, vars));
+      },
+      Kind::Friend => errors.push(LintError {
+        kind: LintErrorTypes::InterfaceFriendDeclaration(class_name.to_string(), child.name.clone()),
+        range: child.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      }),
+      Kind::Type|Kind::Conditional|Kind::Reference(_)|Kind::LintError(_) => (),
+      Kind::Unhandled(element) => errors.push(LintError {
+        kind: LintErrorTypes::ParserUnhandled(element.clone()),
+        range: child.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      }),
+      _ => todo!("{:?}", child.kind),
+    }
+  }
+
+  // Members of classes that declare pure virtual methods or a virtual
+  // destructor are already reported as attributes or non virtual methods
+  let concrete_members = get_concrete_members(node, class_name);
+  if !concrete_members.is_empty() && !has_pure_virtual_member(node) {
+    let members = concrete_members.iter().map(|name| format!("'{name}'")).collect::<Vec<_>>().join(", ");
+    errors.push(LintError {
+      kind: LintErrorTypes::InterfaceNameOnConcreteClass(class_name.to_string(), members, prefix.to_string()),
+      range: node.range.clone(),
+      file_path: code.file_path.clone(),
+      secondary_labels: vec![],
+      notes: vec![],
+    });
+  }
+
+  if !has_default_destructor {
+    errors.push(LintError {
+      kind: LintErrorTypes::CppAbstractClassMissingDefaultDestructor(class_name.to_string()),
+      range: node.range.clone(),
+      file_path: code.file_path.clone(),
+      secondary_labels: vec![],
+      notes: vec![],
+    });
+  }
+
+  errors
+}
+
+/// Abstract classes consist only of a header. Any `.cpp` file that
+/// defines members of an abstract class (or is named like one) violates
+/// this.
+fn check_abstract_class_implementation_files(ast: &[AST], config: &Config) -> Vec<LintError> {
+  let mut errors = vec![];
+  let abstract_classes = get_abstract_class_declarations(ast);
+
+  for file in ast.iter() {
+    if !file.name.ends_with(".cpp") {
+      continue;
+    }
+
+    let mut reported = HashSet::new();
+    for node in file.children.iter() {
+      let Kind::Function(Function { in_external_namespace: Some(class_name), .. }) = &node.kind else {
+        continue;
+      };
+      if let Some(declaration) = abstract_classes.get(class_name) {
+        if reported.insert(class_name.clone()) {
+          errors.push(abstract_class_implementation_error(class_name, node.range.clone(), file, declaration));
+        }
+      }
+    }
+
+    let file_name = file.name.rsplit('/').next().unwrap_or(&file.name);
+    let class_name = file_name.trim_end_matches(".cpp");
+    if class_name.starts_with(&config.abstract_prefix) && !reported.contains(class_name) {
+      let range = get_implementation_file_range(file, class_name);
+      let lint = match abstract_classes.get(class_name) {
+        Some(declaration) => abstract_class_implementation_error(class_name, range, file, declaration),
+        None => LintError {
+          kind: LintErrorTypes::CppAbstractClassHasImplementationFile(class_name.to_string()),
+          range,
+          file_path: file.name.clone(),
+          secondary_labels: vec![],
+          notes: vec![],
+        },
+      };
+      errors.push(lint);
+    }
+  }
+
+  errors
+}
+
+/// Structures and enums that belong to the context of an abstract class
+/// (i.e. are used as parameter or return type of its methods) should be
+/// defined inside of the class instead of the global namespace. Types
+/// used by several abstract classes are shared and left alone.
+fn check_types_defined_in_abstract_classes(ast: &[AST]) -> Vec<LintError> {
+  let mut errors = vec![];
+  let mut global_types = HashMap::new();
+  for file in ast.iter() {
+    for node in file.children.iter().filter(|node| node.kind == Kind::Type && !node.name.is_empty()) {
+      global_types.insert(node.name.trim().to_string(), (file.name.clone(), node.range.clone()));
+    }
+  }
+
+  let mut users: HashMap<String, Vec<(&str, &str, &AST)>> = HashMap::new();
+  for file in ast.iter() {
+    for class in file.children.iter().filter(|node| matches!(node.kind, Kind::Class(cl) if cl.is_abstract)) {
+      let mut used_types = HashSet::new();
+      for method in class.children.iter().filter(|child| matches!(child.kind, Kind::Function(_))) {
+        let signature_types = method.dependencies.iter()
+          .chain(get_parameters(method).flat_map(|param| param.dependencies.iter()));
+        for type_ref in signature_types {
+          let type_name = type_ref.name.trim();
+          if global_types.contains_key(type_name) && used_types.insert(type_name.to_string()) {
+            users.entry(type_name.to_string()).or_default().push((&class.name, &file.name, method));
+          }
+        }
+      }
+    }
+  }
+
+  for (type_name, users) in users.iter() {
+    let [(class_name, file_path, method)] = users.as_slice() else {
+      continue;
+    };
+    let (type_file, type_range) = &global_types[type_name];
+    errors.push(LintError {
+      kind: LintErrorTypes::InterfaceTypesDefinedInClass(class_name.to_string(), type_name.clone()),
+      range: type_range.clone(),
+      file_path: type_file.clone(),
+      secondary_labels: vec![LintLabel {
+        message: format!("'{type_name}' is used by '{class_name}' here"),
+        range: method.range.clone(),
+        file_path: file_path.to_string(),
+      }],
+      notes: vec![],
+    });
+  }
+  errors.sort_by(|a, b| (&a.file_path, a.range.start).cmp(&(&b.file_path, b.range.start)));
+
+  errors
+}
+
+fn abstract_class_implementation_error(class_name: &str, range: core::ops::Range<usize>, file: &AST, declaration: &(String, core::ops::Range<usize>)) -> LintError {
+  let (header_path, header_range) = declaration;
+  LintError {
+    kind: LintErrorTypes::CppAbstractClassHasImplementationFile(class_name.to_string()),
+    range,
+    file_path: file.name.clone(),
+    secondary_labels: vec![LintLabel {
+      message: format!("abstract class '{class_name}' is declared here"),
+      range: header_range.clone(),
+      file_path: header_path.clone(),
+    }],
+    notes: vec![],
+  }
+}
+
+/// The include of the class header or else the first definition of an
+/// implementation file that contains no methods of the class
+fn get_implementation_file_range(file: &AST, class_name: &str) -> core::ops::Range<usize> {
+  let header_include = file.dependencies.iter().find(|include| {
+    let header = include.name.trim_matches('"');
+    let header = header.rsplit('/').next().unwrap_or(header);
+    header.rsplit_once('.').is_some_and(|(stem, _extension)| stem == class_name)
+  });
+
+  header_include.or(file.children.iter().find(|node| !node.range.is_empty()))
+    .map(|node| node.range.clone())
+    .unwrap_or(0..0)
+}
+
+/// File path and range of every abstract class declaration in the project
+fn get_abstract_class_declarations(ast: &[AST]) -> HashMap<String, (String, core::ops::Range<usize>)> {
+  let mut classes = HashMap::default();
+
+  for file in ast.iter() {
+    for node in file.children.iter() {
+      if let Kind::Class(cl) = node.kind {
+        if cl.is_abstract {
+          classes.insert(node.name.clone(), (file.name.clone(), node.range.clone()));
+        }
+      }
+    }
+  }
+
+  classes
+}
+
+/// Attributes and methods with an implementation. Constructors are left
+/// out as they are reported on their own.
+fn get_concrete_members(class: &AST, class_name: &str) -> Vec<String> {
+  class.children.iter().filter_map(|child| match &child.kind {
+    Kind::Variable(vl) if !vl.is_const => Some(child.name.clone()),
+    Kind::Function(fun) if !fun.is_virtual && child.name != class_name && !is_special_member_without_behaviour(child, fun, class_name) => {
+      Some(child.name.clone())
+    }
+    _ => None,
+  }).collect()
+}
+
+/// Constructors and assignment operators declared with `= delete` or
+/// `= default` only control how a class can be copied or moved
+fn is_special_member_without_behaviour(node: &AST, fun: &Function, class_name: &str) -> bool {
+  (fun.is_deleted || fun.is_defaulted) && (node.name == class_name || node.name == "operator=")
+}
+
+/// Pure virtual methods or a default virtual destructor
+fn has_pure_virtual_member(class: &AST) -> bool {
+  class.children.iter().any(|child| matches!(&child.kind, Kind::Function(fun) if fun.is_virtual))
+}
+
+/// A class that only consists of pure virtual methods is an abstract
+/// class, no matter how it is named.
+fn is_semantically_abstract(class: &AST) -> bool {
+  let mut has_pure_virtual_method = false;
+
+  for child in class.children.iter() {
+    match &child.kind {
+      Kind::Function(fun) if is_special_member_without_behaviour(child, fun, &class.name) => (),
+      Kind::Function(fun) => {
+        if !fun.is_virtual {
+          return false;
+        }
+        if !child.name.starts_with('~') {
+          has_pure_virtual_method = true;
+        }
+      }
+      Kind::Variable(vl) if !vl.is_const => return false,
+      _ => (),
+    }
+  }
+
+  has_pure_virtual_method
+}
+
+fn check_derived_class(node: AST, class_name: &str, code: &TextFile, vars: &InScope) -> AST {
+  let mut node = node;
+  let mut errors = vec![];
+  let interface = if node.dependencies.is_empty() {
+    None
+  } else {
+    get_interface_methods(class_name, vars)
+  };
+
+  node.children = node.children.into_iter().map(|child| {
+    match &child.kind {
+      Kind::Variable(vl) => {
+        if vl.visibility != "private" {
+          errors.push(LintError {
+            kind: LintErrorTypes::DerivedClassesAllAttrsPrivate(class_name.to_string(), child.name.clone()),
+            range: child.range.clone(),
+            file_path: code.file_path.clone(),
+            secondary_labels: vec![],
+            notes: vec![],
+          });
+        }
+        child
+      }
+      Kind::Function(fun) => {
+        errors.append(&mut check_function_is_not_virtual(&child, &fun, class_name, code));
+        errors.append(&mut check_data_structures_by_value(&child, fun, class_name, code, vars));
+        if let Some(interface) = &interface {
+          let is_special = child.name == class_name || child.name.starts_with('~') || child.name.starts_with("operator");
+          if fun.visibility == "public" && !is_special && !interface.contains(child.name.trim()) {
+            errors.push(LintError {
+              kind: LintErrorTypes::DerivedClassesOnlyInterfacePublic(class_name.to_string(), child.name.clone()),
+              range: child.range.clone(),
+              file_path: code.file_path.clone(),
+              secondary_labels: vec![],
+              notes: vec![],
+            });
+          }
+        }
+        add_lint_errors_for_function(child, |name| {
+          let empty = HashSet::default();
+          let class_vars = vars.namespaces.get(&node.name).unwrap_or(&empty);
+          class_vars.contains(name) || vars.constants.contains(name)
+        }, code, false)
+      },
+      Kind::Friend => {
+        errors.push(LintError {
+          kind: LintErrorTypes::DerivedClassesFriendDeclaration(class_name.to_string(), child.name.clone()),
+          range: child.range.clone(),
+          file_path: code.file_path.clone(),
+          secondary_labels: vec![],
+          notes: vec![],
+        });
+        child
+      }
+      Kind::LintError(_) => child,
+      Kind::Unhandled(element) => {
+        errors.push(LintError {
+          kind: LintErrorTypes::ParserUnhandled(element.clone()),
+          range: child.range.clone(),
+          file_path: code.file_path.clone(),
+          secondary_labels: vec![],
+          notes: vec![],
+        });
+        child
+      },
+      Kind::Type|Kind::Conditional|Kind::Reference(_) => child,
+      _ => unreachable!(),
+    }
+  }).collect();
+
+  for err in errors.into_iter() {
+    node.children.push(AST {
+      kind: Kind::LintError(err.kind),
+      range: err.range,
+      ..AST::default()
+    });
+  }
+  node
+}
+
+fn check_derives(class: &AST, code: &TextFile, prefix: &str) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  let class_name = &class.name;
+  for derived_from in class.dependencies.iter() {
+    if !derived_from.name.starts_with(prefix) {
+      errors.push(LintError {
+        kind: LintErrorTypes::CppDerivesAlwaysFromAbstractInterfaces(class_name.to_string()),
+        range: class.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+  }
+
+  errors
+}
+
+/// Data structures that can be queried or set must be passed by value.
+/// Handing out references gives callers access to the internals of the
+/// class.
+fn check_data_structures_by_value(method: &AST, fun: &Function, class_name: &str, code: &TextFile, vars: &InScope) -> Vec<LintError> {
+  let mut errors = vec![];
+  if fun.visibility != "public" {
+    return errors;
+  }
+
+  if fun.returns != Declarator::Value {
+    let empty = HashSet::default();
+    let attributes = vars.namespaces.get(class_name).unwrap_or(&empty);
+    let returns_data_structure = method.dependencies.iter().any(|dep| is_data_structure(&dep.name, vars));
+    if returns_data_structure || is_trivial_accessor(method, attributes) {
+      errors.push(LintError {
+        kind: LintErrorTypes::DataStructuresReturnedByReference(class_name.to_string(), method.name.clone()),
+        range: method.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+  }
+
+  for param in get_parameters(method) {
+    let Kind::Variable(var) = &param.kind else {
+      continue;
+    };
+    let passed_by_reference = var.declarator == Declarator::Reference { is_const: false };
+    if passed_by_reference && param.dependencies.iter().any(|dep| is_data_structure(&dep.name, vars)) {
+      errors.push(LintError {
+        kind: LintErrorTypes::DataStructuresPassedByReference(class_name.to_string(), method.name.clone(), param.name.clone()),
+        range: param.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+  }
+
+  errors
+}
+
+/// Methods defined outside of the class declaration (`Foo::bar()`) only
+/// reveal in their body whether they hand out an attribute. Returned data
+/// structures are already reported at the declaration.
+fn check_out_of_line_accessor(method: &AST, fun: &Function, code: &TextFile, vars: &InScope) -> Vec<LintError> {
+  let Some(class_name) = &fun.in_external_namespace else {
+    return vec![];
+  };
+  let Some(class) = vars.classes.get(class_name) else {
+    return vec![];
+  };
+  if fun.returns == Declarator::Value || !class.public_methods.contains(method.name.trim()) {
+    return vec![];
+  }
+
+  let empty = HashSet::default();
+  let attributes = vars.namespaces.get(class_name).unwrap_or(&empty);
+  let returns_data_structure = method.dependencies.iter().any(|dep| is_data_structure(&dep.name, vars));
+  if returns_data_structure || !is_trivial_accessor(method, attributes) {
+    return vec![];
+  }
+
+  vec![LintError {
+    kind: LintErrorTypes::DataStructuresReturnedByReference(class_name.to_string(), method.name.clone()),
+    range: method.range.clone(),
+    file_path: code.file_path.clone(),
+    secondary_labels: vec![],
+    notes: vec![],
+  }]
+}
+
+/// Abstract interfaces depend on other abstractions only. Concrete classes
+/// in the signature of a method couple every implementation to them.
+fn check_abstract_signature_types(method: &AST, class_name: &str, code: &TextFile, vars: &InScope) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  let return_types = method.dependencies.iter().map(|dep| (dep, method));
+  let param_types = get_parameters(method).flat_map(|param| param.dependencies.iter().map(move |dep| (dep, param)));
+  for (type_ref, user) in return_types.chain(param_types) {
+    if let Some(type_name) = get_concrete_class(&type_ref.name, vars) {
+      errors.push(LintError {
+        kind: LintErrorTypes::InterfaceConcreteTypeInSignature(class_name.to_string(), method.name.clone(), type_name.to_string()),
+        range: user.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+  }
+
+  errors
+}
+
+fn get_concrete_class<'a>(type_name: &'a str, vars: &InScope) -> Option<&'a str> {
+  let type_name = strip_template_arguments(type_name);
+  let class_name = type_name.rsplit("::").next().unwrap_or(type_name);
+  match vars.classes.get(class_name) {
+    Some(class) if !class.is_abstract => Some(class_name),
+    _ => None,
+  }
+}
+
+/// Types of the standard library that manage resources and therefore are
+/// no plain old data
+const NON_POD_STANDARD_TYPES: [&str; 12] = [
+  "std::string", "std::vector", "std::list", "std::deque", "std::map", "std::set",
+  "std::unordered_map", "std::unordered_set", "std::unique_ptr", "std::shared_ptr",
+  "std::function", "std::any",
+];
+
+/// Types next to abstract classes are part of the interface and must not
+/// bring in any behaviour or concrete dependencies.
+fn check_structs_in_abstract_headers(ast: &[AST], vars: &InScope) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  for file in ast.iter() {
+    let abstract_classes: Vec<_> = file.children.iter().filter(|node| matches!(node.kind, Kind::Class(cl) if cl.is_abstract)).collect();
+    if abstract_classes.is_empty() {
+      continue;
+    }
+    let nested_types = abstract_classes.iter().flat_map(|class| class.children.iter());
+    let structs = file.children.iter().chain(nested_types).filter(|node| node.kind == Kind::Type);
+    for structure in structs {
+      for member in structure.children.iter() {
+        let kind = match &member.kind {
+          Kind::Function(_) => LintErrorTypes::InterfaceStructHasMethod(structure.name.clone(), member.name.clone()),
+          Kind::Variable(var) if var.declarator == Declarator::Value && member.dependencies.iter().any(|dep| is_non_pod_type(&dep.name, vars)) => {
+            LintErrorTypes::InterfaceStructNonPodMember(structure.name.clone(), member.name.clone())
+          }
+          _ => continue,
+        };
+        errors.push(LintError {
+          kind,
+          range: member.range.clone(),
+          file_path: file.name.clone(),
+          secondary_labels: vec![],
+          notes: vec![],
+        });
+      }
+    }
+  }
+
+  errors
+}
+
+fn is_non_pod_type(type_name: &str, vars: &InScope) -> bool {
+  let type_name = strip_template_arguments(type_name);
+  NON_POD_STANDARD_TYPES.contains(&type_name) || vars.classes.contains_key(type_name.rsplit("::").next().unwrap_or(type_name))
+}
+
+/// The parameters are extracted before anything from the function body
+fn get_parameters(function: &AST) -> impl Iterator<Item = &AST> {
+  function.children.iter().take_while(|child| matches!(child.kind, Kind::Variable(_)))
+}
+
+fn is_data_structure(type_name: &str, vars: &InScope) -> bool {
+  type_name
+    .split(|c: char| !c.is_alphanumeric() && c != '_')
+    .any(|name| vars.types.contains(name))
+}
+
+fn check_function_is_virtual(field: &AST, fun: &Function, class_name: &str, code: &TextFile) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  errors.append(&mut prohibit_init_function(field, class_name, code));
+
+  let function_code = code.content[field.range.start..field.range.end].to_string();
+  if !fun.is_virtual {
+    if !function_code.starts_with("virtual") {
+      errors.push(LintError {
+        kind: LintErrorTypes::CppAbstractClassMethodNotVirtual(class_name.to_string(), function_code.clone()),
+        range: field.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+
+    if !function_code.replace(" ", "").ends_with("=0;") {
+      errors.push(LintError {
+        kind: LintErrorTypes::CppAbstractClassMethodMissingVirtualEnding(class_name.to_string(), function_code),
+        range: field.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+  }
+
+  errors
+}
+
+fn check_function_is_not_virtual(field: &AST, fun: &Function, class_name: &str, code: &TextFile) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  if !fun.is_virtual {
+    if field.name.starts_with("virtual") {
+      errors.push(LintError {
+        kind: LintErrorTypes::CppDerivedClassMethodIsVirtual(class_name.to_string(), field.name.clone()),
+        range: field.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+
+    if field.name.replace(" ", "").ends_with("=0;") {
+      errors.push(LintError {
+        kind: LintErrorTypes::CppDerivedClassMethodHasVirtualEnding(class_name.to_string(), field.name.clone()),
+        range: field.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+  }
+
+  errors.append(&mut prohibit_init_function(field, class_name, code));
+
+  errors
+}
+
+fn prohibit_init_function(field: &AST, class_name: &str, code: &TextFile) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  if field.name.contains("init") {
+    errors.push(LintError {
+      kind: LintErrorTypes::AvoidInitMethods(class_name.to_string()),
+      range: field.range.clone(),
+      file_path: code.file_path.clone(),
+      secondary_labels: vec![],
+      notes: vec![],
+    });
+  }
+
+  errors
+}
+
+fn error_message_from_ast(input: AST, code: &TextFile, vars: &InScope) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  match &input.kind {
+    Kind::File { content } => {
+      let source = TextFile {
+        content: content.clone(),
+        file_path: input.name.clone(),
+      };
+      errors.append(&mut error_message_from_global_codechunk(input.children, &source, vars));
+    }
+    _ => errors.append(&mut get_lint_errors_for_node(&input, code, vars)),
+  }
+
+  errors
+}
+
+fn get_lint_errors_for_node(input: &AST, code: &TextFile, vars: &InScope) -> Vec<LintError> {
+  let mut errors = vec![];
+  match &input.kind {
+    Kind::LintError(err) => errors.push(LintError {
+      kind: err.clone(),
+      range: input.range.clone(),
+      file_path: code.file_path.clone(),
+      secondary_labels: vec![],
+      notes: vec![],
+    }),
+    Kind::Unhandled(element) => errors.push(LintError {
+      kind: LintErrorTypes::ParserUnhandled(element.clone()),
+      range: input.range.clone(),
+      file_path: code.file_path.clone(),
+      secondary_labels: vec![],
+      notes: vec![],
+    }),
+    _ => {
+      for child in input.children.iter() {
+        errors.append(&mut get_lint_errors_for_node(child, code, vars));
+      }
+    }
+  };
+  errors
+}
+
+fn add_lint_errors_for_node(node: AST, code: &TextFile, vars: &InScope, has_main_entrypoint: bool, config: &Config) -> AST {
+  let mut node = node;
+  let mut errors = vec![];
+  let name = &node.name.clone();
+  match &node.kind.clone() {
+    Kind::Class(ref cl) => {
+      if cl.is_abstract {
+        errors.append(&mut check_abstract_class(&node, &name, code, vars, &config.abstract_prefix));
+      } else {
+        let is_abstract_without_prefix = is_semantically_abstract(&node);
+        if is_abstract_without_prefix {
+          errors.push(LintError {
+            kind: LintErrorTypes::InterfaceNameMissingAbstractPrefix(name.to_string(), config.abstract_prefix.clone()),
+            range: node.range.clone(),
+            file_path: code.file_path.clone(),
+            secondary_labels: vec![],
+            notes: vec![],
+          });
+        }
+        node = check_derived_class(node, &name, code, vars);
+        if node.dependencies.len() == 0 && !is_abstract_without_prefix {
+          errors.push(LintError {
+            kind: LintErrorTypes::DeriveFromAbstractInterface(name.to_string()),
+            range: node.range.clone(),
+            file_path: code.file_path.clone(),
+            secondary_labels: vec![],
+            notes: vec![],
+          });
+        }
+      }
+      errors.append(&mut check_derives(&node, code, &config.abstract_prefix));
+      errors.append(&mut check_conditional_compilation(&node, code));
+      errors.append(&mut check_hidden_global_state(&node, code));
+    }
+    Kind::Function(fun) => {
+      errors.append(&mut check_conditional_compilation(&node, code));
+      errors.append(&mut check_hidden_global_state(&node, code));
+      errors.append(&mut check_out_of_line_accessor(&node, fun, code, vars));
+      node = match &fun.in_external_namespace {
+        None => add_lint_errors_for_function(node, |name| { vars.constants.contains(name) }, code, has_main_entrypoint),
+        Some(namespace) => add_lint_errors_for_function(node, |name| {
+          let empty = HashSet::default();
+          let class_vars = vars.namespaces.get(namespace).unwrap_or(&empty);
+          class_vars.contains(name) || vars.constants.contains(name)
+        }, code, has_main_entrypoint),
+      };
+    },
+    Kind::Type => errors.append(&mut check_conditional_compilation(&node, code)),
+    Kind::Conditional|Kind::Macro(_)|Kind::Reference(_)|Kind::LintError(_) => (),
+    Kind::Variable(var) => {
+      if var.storage.is_extern && !has_main_entrypoint {
+        errors.push(LintError {
+          kind: LintErrorTypes::GlobalVariablesUsage(node.name.clone()),
+          range: node.range.clone(),
+          file_path: code.file_path.clone(),
+          secondary_labels: vec![],
+          notes: vec![],
+        });
+      } else if !var.is_const && !var.storage.is_extern && !has_main_entrypoint {
+        errors.push(LintError {
+          kind: LintErrorTypes::GlobalVariablesDeclaration(node.name.clone()),
+          range: node.range.clone(),
+          file_path: code.file_path.clone(),
+          secondary_labels: vec![],
+          notes: vec![],
+        });
+      };
+    }
+    Kind::Unhandled(element) => errors.push(LintError {
+      kind: LintErrorTypes::ParserUnhandled(element.clone()),
+      range: node.range.clone(),
+      file_path: code.file_path.clone(),
+      secondary_labels: vec![],
+      notes: vec![],
+    }),
+    _ => todo!("{:?}", node.kind)
+  };
+
+  for err in errors.into_iter() {
+    node.children.push(AST {
+      kind: Kind::LintError(err.kind),
+      range: err.range,
+      ..AST::default()
+    });
+  }
+  node
+}
+
+/// Conditional compilation is only allowed on file level. Nested blocks
+/// are reported for the innermost class, function or struct.
+fn check_conditional_compilation(scope: &AST, code: &TextFile) -> Vec<LintError> {
+  let mut errors = vec![];
+  for child in scope.children.iter() {
+    match child.kind {
+      Kind::Conditional => errors.push(LintError {
+        kind: LintErrorTypes::BitRotConditionalCompilation(scope.name.clone(), child.name.clone()),
+        range: child.range.clone(),
+        file_path: code.file_path.clone(),
+        secondary_labels: vec![],
+        notes: vec![],
+      }),
+      Kind::Class(_)|Kind::Function(_)|Kind::Type => errors.append(&mut check_conditional_compilation(child, code)),
+      _ => (),
+    }
+  }
+
+  errors
+}
+
+/// Mutable static attributes, static variables in functions and singletons
+/// are global variables in disguise
+fn check_hidden_global_state(node: &AST, code: &TextFile) -> Vec<LintError> {
+  let mut errors = vec![];
+  for child in node.children.iter() {
+    let kind = match (&node.kind, &child.kind) {
+      (Kind::Class(_), Kind::Variable(var)) if var.storage.is_static && !var.is_const => {
+        LintErrorTypes::GlobalVariablesStaticAttribute(node.name.clone(), child.name.clone())
+      }
+      (Kind::Class(_), Kind::Function(fun)) => {
+        errors.append(&mut check_hidden_global_state(child, code));
+        let returns_own_instance = child.dependencies.iter().any(|dep| strip_template_arguments(&dep.name) == node.name);
+        if !(fun.storage.is_static && fun.returns != Declarator::Value && returns_own_instance) {
+          continue;
+        }
+        LintErrorTypes::GlobalVariablesSingleton(node.name.clone(), child.name.clone())
+      }
+      (Kind::Function(_), Kind::Variable(var)) if var.storage.is_static && !var.is_const => {
+        LintErrorTypes::GlobalVariablesStaticLocal(node.name.clone(), child.name.clone())
+      }
+      _ => continue,
+    };
+    errors.push(LintError {
+      kind,
+      range: child.range.clone(),
+      file_path: code.file_path.clone(),
+      secondary_labels: vec![],
+      notes: vec![],
+    });
+  }
+
+  errors
+}
+
+fn add_lint_errors_for_function<F>(input: AST, in_scope: F, code: &TextFile, has_main_entrypoint: bool) -> AST
+where
+  F: Fn(&str) -> bool,
+{
+  let mut input = input;
+  let mut errors = vec![];
+  let vars_in_scope = get_vars_in_scope(&input);
+
+  input.children = input.children.into_iter().map(|mut node| {
+    match &node.kind {
+      Kind::Reference(ref_kind) => {
+        use Reference::*;
+        match ref_kind {
+          Read|Write => if !vars_in_scope.contains(&node.name) && !in_scope(&node.name) && !has_main_entrypoint {
+            node.children.push(AST {
+              kind: Kind::LintError(LintErrorTypes::GlobalVariablesUsage(node.name.clone())),
+              range: node.range.clone(),
+              ..AST::default()
+            });
+          }
+          Call|TypeRead|Depend|Instantiate => (),
+        }
+        node
+      }
+      Kind::Variable(_var) => node,
+      Kind::Type|Kind::Conditional => node,
+      Kind::Unhandled(element) => {
+        errors.push(LintError {
+          kind: LintErrorTypes::ParserUnhandled(element.clone()),
+          range: node.range.clone(),
+          file_path: code.file_path.clone(),
+          secondary_labels: vec![],
+          notes: vec![],
+        });
+        node
+      },
+      _ => todo!("node {:?} not yet implemented", node.kind)
+    }
+  }).collect();
+
+  for err in errors.into_iter() {
+    input.children.push(AST {
+      kind: Kind::LintError(err.kind),
+      range: err.range,
+      ..AST::default()
+    });
+  }
+  input
+}
+
+fn filter_references_in_function<F>(input: AST, in_scope: F) -> AST
+where
+  F: Fn(&str) -> bool,
+{
+  let vars_in_scope = get_vars_in_scope(&input);
+
+  let AST { name, kind, children, dependencies, range, instructions } = input;
+  let children = children.into_iter().filter(|node| {
+    match &node.kind {
+      Kind::Reference(ref_kind) => {
+        use Reference::*;
+        match ref_kind {
+          Read|Write => !vars_in_scope.contains(&node.name) && !in_scope(&node.name),
+          Call|TypeRead|Depend|Instantiate => true,
+        }
+      }
+      _ => true,
+    }
+  }).collect();
+  AST {
+    name,
+    kind,
+    children,
+    dependencies,
+    range,
+    instructions
+  }
+}
+
+fn get_variables_from_all_classes(ast: &Vec<AST>) -> HashMap<String, HashSet<String>> {
+  let mut vars = HashMap::default();
+
+  for node in ast.iter() {
+    match &node.kind {
+      Kind::File { content: _ } => vars.extend(get_variables_from_all_classes(&node.children)),
+      Kind::Class(_) => {
+        let class_vars = get_vars_in_scope(node);
+        vars.insert(node.name.clone(), class_vars);
+      }
+      _ => (),
+    }
+  }
+
+  vars
+}
+
+fn get_classes_from_all_files(ast: &[AST]) -> HashMap<String, ClassScope> {
+  let mut classes = HashMap::default();
+
+  for node in ast.iter() {
+    match &node.kind {
+      Kind::File { content: _ } => classes.extend(get_classes_from_all_files(&node.children)),
+      Kind::Class(cl) => {
+        let methods = node.children.iter().filter_map(|child| match child.kind {
+          Kind::Function(_) => Some(child.name.trim().to_string()),
+          _ => None,
+        }).collect();
+        let public_methods = node.children.iter().filter_map(|child| match &child.kind {
+          Kind::Function(fun) if fun.visibility == "public" => Some(child.name.trim().to_string()),
+          _ => None,
+        }).collect();
+        let bases = node.dependencies.iter().map(|base| strip_template_arguments(&base.name).to_string()).collect();
+        classes.insert(node.name.clone(), ClassScope {
+          is_abstract: cl.is_abstract,
+          methods,
+          public_methods,
+          bases,
+          attributes: get_variable_types(&node.children),
+        });
+      }
+      _ => (),
+    }
+  }
+
+  classes
+}
+
+fn get_types_from_all_files(ast: &[AST]) -> HashSet<String> {
+  let mut types = HashSet::default();
+
+  for node in ast.iter() {
+    match &node.kind {
+      Kind::File { content: _ }|Kind::Class(_) => types.extend(get_types_from_all_files(&node.children)),
+      Kind::Type => {
+        types.insert(node.name.trim().to_string());
+      }
+      _ => (),
+    }
+  }
+
+  types
+}
+
+fn strip_template_arguments(name: &str) -> &str {
+  name.split_once('<').map(|(name, _)| name).unwrap_or(name).trim()
+}
+
+/// All methods a class inherits from its (transitive) base classes. If a
+/// base class is not part of the parsed sources the interface is unknown.
+fn get_interface_methods(class_name: &str, vars: &InScope) -> Option<HashSet<String>> {
+  let mut methods = HashSet::default();
+
+  for base in vars.classes.get(class_name)?.bases.iter() {
+    methods.extend(vars.classes.get(base)?.methods.iter().cloned());
+    methods.extend(get_interface_methods(base, vars)?);
+  }
+
+  Some(methods)
+}
+
+fn get_constants(ast: &Vec<AST>) -> HashSet<String> {
+  let mut constants = HashSet::default();
+
+  for node in ast.iter() {
+    match &node.kind {
+      Kind::File { content: _ } => constants.extend(get_constants_in_scope(node)),
+      _ => (),
+    }
+  }
+
+  constants
+}
+
+fn get_vars_in_scope(input: &AST) -> HashSet<String> {
+  input.children.iter().filter_map(|node| match node.kind {
+    Kind::Variable(_) => Some(node.name.trim().to_string()),
+    _ => None,
+  }).collect()
+}
+
+/// Maps the variables declared in `nodes` to the name of their type
+fn get_variable_types(nodes: &[AST]) -> HashMap<String, String> {
+  nodes.iter().filter_map(|node| match node.kind {
+    Kind::Variable(_) if !node.dependencies.is_empty() => {
+      let type_name = node.dependencies.iter().map(|dep| dep.name.as_str()).collect::<Vec<_>>().join(" ");
+      Some((node.name.trim().to_string(), type_name))
+    }
+    _ => None,
+  }).collect()
+}
+
+fn get_constants_in_scope(input: &AST) -> HashSet<String> {
+  input.children.iter().filter_map(|node| match node.kind {
+    Kind::Variable(ref v) => if v.is_const {
+      Some(node.name.trim().to_string())
+    } else {
+      None
+    }
+    _ => None,
+  }).collect()
+}
+
+struct TextFile {
+  pub content: String,
+  pub file_path: String,
+}
+
+struct InScope {
+  pub constants: HashSet<String>,
+  pub namespaces: HashMap<String, HashSet<String>>,
+  pub classes: HashMap<String, ClassScope>,
+  /// structs and enums
+  pub types: HashSet<String>,
+}
+
+struct ClassScope {
+  pub is_abstract: bool,
+  pub methods: HashSet<String>,
+  pub public_methods: HashSet<String>,
+  pub bases: Vec<String>,
+  /// type of each attribute
+  pub attributes: HashMap<String, String>,
+}
+
+/// Global variables are only allowed in the file containing the main
+/// entrypoint. Maps their names to the place of their definition.
+fn get_globals_of_main_files(ast: &[AST], config: &Config) -> HashMap<String, (String, Range<usize>)> {
+  let mut globals = HashMap::new();
+
+  for file in ast.iter().filter(|file| check_if_has_main_entrypoint(file, &config.entry_points)) {
+    for node in file.children.iter() {
+      match &node.kind {
+        Kind::Variable(var) if !var.is_const && !var.storage.is_extern => {
+          globals.entry(node.name.trim().to_string()).or_insert_with(|| (file.name.clone(), node.range.clone()));
+        }
+        _ => (),
+      }
+    }
+  }
+
+  globals
+}
+
+/// Object-like macros with a plain value are constants without a type.
+/// Macros accessing global variables hide that access at every expansion.
+fn check_macros(ast: &[AST], config: &Config) -> Vec<LintError> {
+  let mut errors = vec![];
+  let globals: HashSet<&str> = ast.iter()
+    .flat_map(|file| file.children.iter())
+    .filter(|node| matches!(&node.kind, Kind::Variable(var) if !var.is_const))
+    .map(|node| node.name.trim())
+    .collect();
+  let constants: HashSet<&str> = ast.iter()
+    .flat_map(|file| file.children.iter())
+    .filter(|node| matches!(&node.kind, Kind::Variable(var) if var.is_const))
+    .map(|node| node.name.trim())
+    .collect();
+
+  for file in ast.iter() {
+    let has_main_entrypoint = check_if_has_main_entrypoint(file, &config.entry_points);
+    for node in file.children.iter() {
+      let Kind::Macro(macro_def) = &node.kind else {
+        continue;
+      };
+      if is_macro_constant(node, macro_def, &constants) {
+        errors.push(LintError {
+          kind: LintErrorTypes::GlobalVariablesMacroConstant(node.name.clone()),
+          range: node.range.clone(),
+          file_path: file.name.clone(),
+          secondary_labels: vec![],
+          notes: vec![],
+        });
+      }
+      if has_main_entrypoint {
+        continue;
+      }
+      for reference in node.children.iter() {
+        let accesses_global = matches!(reference.kind, Kind::Reference(Reference::Read|Reference::Write))
+          && globals.contains(reference.name.as_str());
+        if accesses_global {
+          errors.push(LintError {
+            kind: LintErrorTypes::GlobalVariablesUsage(reference.name.clone()),
+            range: reference.range.clone(),
+            file_path: file.name.clone(),
+            secondary_labels: vec![],
+            notes: vec![format!("the global variable is accessed by macro '{}' and therefore by every expansion of it", node.name)],
+          });
+        }
+      }
+    }
+  }
+
+  errors
+}
+
+/// Macros that only stand for a value: literals combined with other
+/// constants (upper case names like `A0` or `constexpr` constants of the
+/// project). Bodies with statements (`20;`) or calls are no constants.
+fn is_macro_constant(node: &AST, macro_def: &Macro, constants: &HashSet<&str>) -> bool {
+  let Some(value) = macro_def.value.as_ref().filter(|value| !value.is_empty()) else {
+    return false;
+  };
+  if macro_def.is_function_like || value.contains([';', '{', '}']) {
+    return false;
+  }
+
+  node.children.iter().all(|reference| {
+    let name = reference.name.as_str();
+    reference.kind == Kind::Reference(Reference::Read)
+      && (!name.contains(|c: char| c.is_lowercase()) || constants.contains(name))
+  })
+}
+
+/// Other files using globals of the main file (by `extern` or directly)
+/// point to the definition of the global
+fn label_global_variable_definitions(errors: &mut [LintError], globals: &HashMap<String, (String, Range<usize>)>) {
+  for error in errors.iter_mut() {
+    if let LintErrorTypes::GlobalVariablesUsage(name) = &error.kind {
+      if let Some((file_path, range)) = globals.get(name.trim()) {
+        if *file_path != error.file_path {
+          error.secondary_labels.push(LintLabel {
+            message: format!("'{}' is defined here", name.trim()),
+            range: range.clone(),
+            file_path: file_path.clone(),
+          });
+        }
+      }
+    }
+  }
+}
+
+fn check_if_has_main_entrypoint(input: &AST, entry_points: &[String]) -> bool {
+  let mut has_setup = false;
+  let mut has_loop = false;
+  for node in input.children.iter() {
+    match node.kind {
+      Kind::Function(_) => match node.name.trim() {
+        "main" => {
+          return true;
+        }
+        "setup" => {
+          if has_loop { return true; }
+          has_setup = true;
+        }
+        "loop" => {
+          if has_setup { return true; }
+          has_loop = true;
+        }
+        name if entry_points.iter().any(|entry_point| entry_point == name) => {
+          return true;
+        }
+        _ => (),
+      }
+      _ => (),
+    }
+  }
+
+  false
+}
+
@@ -0,0 +1,65 @@
+use crate::ast::{AST, Kind, LintError, LintErrorTypes};
+
+use super::{get_parameters, Config};
+
+/// Names of abstract interfaces must not reveal how they are implemented
+/// (e.g. `send_modbus_request`). Method names and parameter types are
+/// split into words which are compared with the configured terms.
+pub fn check_forbidden_terms(ast: &[AST], config: &Config) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  for file in ast.iter() {
+    for class in file.children.iter().filter(|node| matches!(node.kind, Kind::Class(cl) if cl.is_abstract)) {
+      for method in class.children.iter().filter(|child| matches!(child.kind, Kind::Function(_))) {
+        let mut names = vec![(method.name.trim(), method)];
+        for param in get_parameters(method) {
+          names.extend(param.dependencies.iter().map(|dep| (dep.name.trim(), param)));
+        }
+
+        for (name, node) in names {
+          if let Some(term) = find_forbidden_term(name, &config.forbidden_terms) {
+            errors.push(LintError {
+              kind: LintErrorTypes::InterfaceRevealsImplementation(class.name.clone(), name.to_string(), term.to_string()),
+              range: node.range.clone(),
+              file_path: file.name.clone(),
+              secondary_labels: vec![],
+              notes: vec![],
+            });
+          }
+        }
+      }
+    }
+  }
+
+  errors
+}
+
+fn find_forbidden_term<'a>(name: &str, terms: &'a [String]) -> Option<&'a str> {
+  let words = split_words(name);
+  terms.iter()
+    .find(|term| words.iter().any(|word| word.eq_ignore_ascii_case(term)))
+    .map(|term| term.as_str())
+}
+
+/// Splits snake_case and CamelCase names (keeping acronyms like `I2C`
+/// or `GPIO` together)
+fn split_words(name: &str) -> Vec<&str> {
+  let mut words = vec![];
+
+  for part in name.split(|c: char| !c.is_ascii_alphanumeric()).filter(|part| !part.is_empty()) {
+    let chars: Vec<char> = part.chars().collect();
+    let mut start = 0;
+    for idx in 1..chars.len() {
+      let lower_to_upper = chars[idx - 1].is_ascii_lowercase() && chars[idx].is_ascii_uppercase();
+      let acronym_end = chars[idx - 1].is_ascii_uppercase() && chars[idx].is_ascii_uppercase()
+        && chars.get(idx + 1).is_some_and(|c| c.is_ascii_lowercase());
+      if lower_to_upper || acronym_end {
+        words.push(&part[start..idx]);
+        start = idx;
+      }
+    }
+    words.push(&part[start..]);
+  }
+
+  words
+}
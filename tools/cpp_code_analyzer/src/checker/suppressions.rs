@@ -0,0 +1,112 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::ast::{AST, Kind, LintError, LintErrorTypes, LintInstruction};
+
+/// All lint instructions of the project together with the file they are
+/// defined in
+pub fn get_lint_instructions(ast: &[AST]) -> Vec<(String, LintInstruction)> {
+  ast.iter()
+    .flat_map(|file| file.instructions.iter().map(|instruction| (file.name.clone(), instruction.clone())))
+    .collect()
+}
+
+/// Removes the errors that are suppressed by a lint instruction and reports
+/// instructions that suppress nothing or are not justified. Expired
+/// instructions no longer suppress anything.
+pub fn apply_lint_instructions(errors: Vec<LintError>, instructions: &[(String, LintInstruction)], today: Option<&str>) -> Vec<LintError> {
+  let is_expired = |instruction: &LintInstruction| match (instruction.until.as_deref(), today) {
+    (Some(until), Some(today)) => until < today,
+    _ => false,
+  };
+  let mut used = vec![false; instructions.len()];
+
+  let mut errors: Vec<LintError> = errors.into_iter().filter(|error| {
+    let suppressed_by = instructions.iter().position(|(file_path, instruction)| {
+      !is_expired(instruction)
+        && *file_path == error.file_path
+        && instruction.ident == error.kind.id()
+        && instruction.scope.contains(&error.range.start)
+    });
+    if let Some(idx) = suppressed_by {
+      used[idx] = true;
+    }
+    suppressed_by.is_none()
+  }).collect();
+
+  for ((file_path, instruction), used) in instructions.iter().zip(used) {
+    if !instruction.has_reason() {
+      errors.push(instruction_error(LintErrorTypes::LintInstructionMissingReason(instruction.ident.clone()), file_path, instruction));
+    }
+    if is_expired(instruction) {
+      let until = instruction.until.clone().unwrap_or_default();
+      errors.push(instruction_error(LintErrorTypes::LintInstructionExpired(instruction.ident.clone(), until), file_path, instruction));
+    } else if !used {
+      errors.push(instruction_error(LintErrorTypes::LintInstructionUnused(instruction.ident.clone()), file_path, instruction));
+    }
+  }
+
+  errors
+}
+
+fn instruction_error(kind: LintErrorTypes, file_path: &str, instruction: &LintInstruction) -> LintError {
+  LintError {
+    kind,
+    range: instruction.range.clone(),
+    file_path: file_path.to_string(),
+    secondary_labels: vec![],
+    notes: vec![],
+  }
+}
+
+/// One line per lint instruction of the project, so that the documented
+/// deviations can be reviewed:
+/// `<file>:<line> <ID> [until=<date>] [ticket=<ticket>] <reason>`
+pub fn report_lint_instructions(ast: &[AST]) -> Vec<String> {
+  let mut report = vec![];
+
+  for file in ast.iter() {
+    let content = match &file.kind {
+      Kind::File { content } => content,
+      _ => continue,
+    };
+    for instruction in file.instructions.iter() {
+      let line = content[..instruction.range.start].matches('\n').count() + 1;
+      let mut entry = format!("{}:{} {}", file.name, line, instruction.ident);
+      if let Some(until) = &instruction.until {
+        entry.push_str(&format!(" until={until}"));
+      }
+      if let Some(ticket) = &instruction.ticket {
+        entry.push_str(&format!(" ticket={ticket}"));
+      }
+      if instruction.has_reason() {
+        entry.push_str(&format!(" {}", instruction.reason));
+      }
+      report.push(entry);
+    }
+  }
+
+  report
+}
+
+/// Current date (UTC) as `YYYY-MM-DD`
+pub fn current_date() -> String {
+  let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0);
+  let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+  format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Converts days since 1970-01-01 into a date of the gregorian calendar
+/// (see <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>)
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+  let days = days + 719_468;
+  let era = days.div_euclid(146_097);
+  let day_of_era = days.rem_euclid(146_097);
+  let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let month_index = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+  let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+  let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+  (year, month, day)
+}
@@ -0,0 +1,81 @@
+use crate::ast::{AST, Kind, LintError, LintErrorTypes};
+
+/// Formatting rules that only depend on the raw text of a file and not on
+/// its syntax tree
+pub fn check_source_text(ast: &[AST]) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  for file in ast.iter() {
+    if let Kind::File { content } = &file.kind {
+      errors.append(&mut check_tab_indentation(content, &file.name));
+      errors.append(&mut check_line_endings(content, &file.name));
+    }
+  }
+
+  errors
+}
+
+fn check_tab_indentation(content: &str, file_path: &str) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  let mut line_start = 0;
+  for (line_number, line) in content.split_inclusive('\n').enumerate() {
+    let indentation = line.len() - line.trim_start_matches([' ', '\t']).len();
+    if line[..indentation].contains('\t') {
+      errors.push(LintError {
+        kind: LintErrorTypes::GeneralSpacesInsteadOfTabs(line_number + 1),
+        range: line_start..line_start + indentation,
+        file_path: file_path.to_string(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+    line_start += line.len();
+  }
+
+  errors
+}
+
+/// The line ending of the first line decides which line ending is expected
+/// in the rest of the file. Only the first deviation is reported.
+fn check_line_endings(content: &str, file_path: &str) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  let mut expects_crlf = None;
+  let mut line_start = 0;
+  for (line_number, line) in content.split_inclusive('\n').enumerate() {
+    let line_end = line_start + line.len();
+    if line.ends_with('\n') {
+      let is_crlf = line.ends_with("\r\n");
+      match expects_crlf {
+        None => expects_crlf = Some(is_crlf),
+        Some(expected) if expected != is_crlf => {
+          let ending_start = if is_crlf { line_end - 2 } else { line_end - 1 };
+          errors.push(LintError {
+            kind: LintErrorTypes::GeneralMixedLineEndings(line_number + 1),
+            range: ending_start..line_end,
+            file_path: file_path.to_string(),
+            secondary_labels: vec![],
+            notes: vec![],
+          });
+          break;
+        }
+        Some(_) => (),
+      }
+    }
+    line_start = line_end;
+  }
+
+  if !content.is_empty() && !content.ends_with('\n') {
+    let last_line_start = content.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+    errors.push(LintError {
+      kind: LintErrorTypes::GeneralMissingFinalNewline,
+      range: last_line_start..content.len(),
+      file_path: file_path.to_string(),
+      secondary_labels: vec![],
+      notes: vec![],
+    });
+  }
+
+  errors
+}
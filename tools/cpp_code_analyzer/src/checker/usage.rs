@@ -0,0 +1,262 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{AST, Kind, Function, LintError, LintErrorTypes, Reference};
+
+use super::{get_interface_methods, get_parameters, get_variable_types, InScope};
+
+/// Callers should use the abstract interface of a class. Calling methods
+/// that only exist in the concrete class is a sign that the interface
+/// needs to be extended (or a new interface is missing).
+pub fn check_concrete_method_calls(ast: &[AST], vars: &InScope) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  for file in ast.iter() {
+    let globals = get_variable_types(&file.children);
+
+    for node in file.children.iter() {
+      match &node.kind {
+        Kind::Function(fun) => {
+          let attributes = fun.in_external_namespace.as_ref()
+            .and_then(|class_name| vars.classes.get(class_name))
+            .map(|class| &class.attributes);
+          errors.append(&mut check_method_calls_in_function(node, attributes, &globals, &file.name, vars));
+        }
+        Kind::Class(_) => {
+          let attributes = vars.classes.get(&node.name).map(|class| &class.attributes);
+          for method in node.children.iter().filter(|child| matches!(child.kind, Kind::Function(_))) {
+            errors.append(&mut check_method_calls_in_function(method, attributes, &globals, &file.name, vars));
+          }
+        }
+        _ => (),
+      }
+    }
+  }
+
+  errors
+}
+
+fn check_method_calls_in_function(function: &AST, attributes: Option<&HashMap<String, String>>, globals: &HashMap<String, String>, file_path: &str, vars: &InScope) -> Vec<LintError> {
+  let mut errors = vec![];
+
+  for (call, class_name) in get_method_calls(function, attributes, globals, vars) {
+    let class = &vars.classes[&class_name];
+    if class.is_abstract || class.bases.is_empty() {
+      continue;
+    }
+    let Some(interface) = get_interface_methods(&class_name, vars) else {
+      continue;
+    };
+    if !interface.contains(&call.name) {
+      errors.push(LintError {
+        kind: LintErrorTypes::DerivedClassesExtraMethodUsage(class_name.clone(), call.name.clone()),
+        range: call.range.clone(),
+        file_path: file_path.to_string(),
+        secondary_labels: vec![],
+        notes: vec![],
+      });
+    }
+  }
+
+  errors
+}
+
+/// Method calls on objects together with the project class of the object
+/// (looked up in the local variables, the attributes and the globals)
+fn get_method_calls<'a>(function: &'a AST, attributes: Option<&HashMap<String, String>>, globals: &HashMap<String, String>, vars: &InScope) -> Vec<(&'a AST, String)> {
+  let locals = get_variable_types(&function.children);
+
+  function.children.iter().filter_map(|call| {
+    let Kind::Reference(Reference::Call) = call.kind else {
+      return None;
+    };
+    let receiver = call.dependencies.first()?;
+    let type_name = locals.get(&receiver.name)
+      .or_else(|| attributes.and_then(|attributes| attributes.get(&receiver.name)))
+      .or_else(|| globals.get(&receiver.name))?;
+    resolve_class(type_name, vars).map(|class_name| (call, class_name.to_string()))
+  }).collect()
+}
+
+/// Finds the project class a type refers to (e.g. `MyClass` in
+/// `std::unique_ptr<MyClass>`)
+fn resolve_class<'a>(type_name: &'a str, vars: &InScope) -> Option<&'a str> {
+  type_name
+    .split(|c: char| !c.is_alphanumeric() && c != '_')
+    .find(|name| vars.classes.contains_key(*name))
+}
+
+/// Getters and setters should only exist if some other class or function
+/// really needs them. Even then a method describing an action is usually
+/// the better choice. Methods of the abstract interface are judged where
+/// they are declared (and count as used when they are called on the
+/// interface or one of its implementations).
+pub fn check_getter_setter_usage(ast: &[AST], vars: &InScope, prefix: &str) -> Vec<LintError> {
+  let mut errors = vec![];
+  let callers = get_method_callers(ast, vars);
+  let definitions = get_out_of_line_definitions(ast);
+  let empty = HashSet::default();
+
+  for file in ast.iter() {
+    for class in file.children.iter().filter(|node| matches!(node.kind, Kind::Class(cl) if cl.is_abstract)) {
+      let implementations: Vec<&String> = vars.classes.keys().filter(|name| derives_from(name, &class.name, vars)).collect();
+
+      for method in class.children.iter().filter(|child| matches!(child.kind, Kind::Function(_))) {
+        if !is_getter_setter_name(&method.name) || exchanges_structure_or_interface(method, vars, prefix) {
+          continue;
+        }
+        let used_outside = std::iter::once(&class.name).chain(implementations.iter().copied())
+          .any(|class_name| is_used_outside(&callers, class_name, &method.name));
+        errors.push(getter_setter_error(&class.name, method, used_outside, &file.name));
+      }
+    }
+
+    for class in file.children.iter().filter(|node| matches!(node.kind, Kind::Class(cl) if !cl.is_abstract)) {
+      let attributes = vars.namespaces.get(&class.name).unwrap_or(&empty);
+      let interface = get_interface_methods(&class.name, vars).unwrap_or_default();
+
+      for method in class.children.iter() {
+        let Kind::Function(fun) = &method.kind else {
+          continue;
+        };
+        if fun.visibility != "public" || method.name == class.name || interface.contains(&method.name) {
+          continue;
+        }
+        let out_of_line = definitions.get(&(class.name.clone(), method.name.clone()));
+        let is_accessor = is_getter_setter_name(&method.name)
+          || is_trivial_accessor(method, attributes)
+          || out_of_line.is_some_and(|definition| is_trivial_accessor(definition, attributes));
+        if !is_accessor {
+          continue;
+        }
+
+        let used_outside = is_used_outside(&callers, &class.name, &method.name);
+        errors.push(getter_setter_error(&class.name, method, used_outside, &file.name));
+      }
+    }
+  }
+
+  errors
+}
+
+fn getter_setter_error(class_name: &str, method: &AST, used_outside: bool, file_path: &str) -> LintError {
+  let kind = if used_outside {
+    LintErrorTypes::AvoidGetterSetter(class_name.to_string(), method.name.clone())
+  } else {
+    LintErrorTypes::AvoidUnusedGetterSetter(class_name.to_string(), method.name.clone())
+  };
+
+  LintError {
+    kind,
+    range: method.range.clone(),
+    file_path: file_path.to_string(),
+    secondary_labels: vec![],
+    notes: vec![],
+  }
+}
+
+fn is_used_outside(callers: &HashMap<(String, String), Vec<Option<String>>>, class_name: &str, method_name: &str) -> bool {
+  callers.get(&(class_name.to_string(), method_name.to_string()))
+    .is_some_and(|owners| owners.iter().any(|owner| owner.as_deref() != Some(class_name)))
+}
+
+/// The styleguide recommends passing whole structures instead of single
+/// values, and accessors of other abstract interfaces hand out a
+/// collaborator. Types outside of the project can only be judged by their
+/// name.
+fn exchanges_structure_or_interface(method: &AST, vars: &InScope, prefix: &str) -> bool {
+  let param_types = get_parameters(method).flat_map(|param| param.dependencies.iter());
+
+  method.dependencies.iter().chain(param_types).any(|dependency| {
+    let type_name = dependency.name.trim();
+    let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+    match vars.classes.get(type_name) {
+      Some(class) => class.is_abstract,
+      None => vars.types.contains(type_name) || (!type_name.is_empty() && type_name.starts_with(prefix)),
+    }
+  })
+}
+
+fn derives_from(class_name: &str, base: &str, vars: &InScope) -> bool {
+  vars.classes.get(class_name).is_some_and(|class| {
+    class.bases.iter().any(|parent| parent == base || derives_from(parent, base, vars))
+  })
+}
+
+fn is_getter_setter_name(name: &str) -> bool {
+  ["get", "set"].iter().any(|prefix| match name.strip_prefix(prefix) {
+    Some(rest) => rest.starts_with('_') || rest.starts_with(|c: char| c.is_uppercase()),
+    None => false,
+  })
+}
+
+/// Bodies that only return an attribute or only assign the parameter to
+/// an attribute
+pub fn is_trivial_accessor(function: &AST, attributes: &HashSet<String>) -> bool {
+  let params: HashSet<&str> = function.children.iter().filter_map(|child| match child.kind {
+    Kind::Variable(_) => Some(child.name.as_str()),
+    _ => None,
+  }).collect();
+  let references: Vec<(&Reference, &str)> = function.children.iter().filter_map(|child| match &child.kind {
+    Kind::Reference(reference) => Some((reference, child.name.as_str())),
+    _ => None,
+  }).collect();
+
+  match (params.len(), references.as_slice()) {
+    (0, [(Reference::Read, name)]) => attributes.contains(*name),
+    (1, [(Reference::Write, attribute), (Reference::Read|Reference::Write, param)]) => {
+      attributes.contains(*attribute) && params.contains(param)
+    }
+    _ => false,
+  }
+}
+
+/// Maps each called method (class and method name) to the classes the
+/// calls are made from (`None` for free functions). Calls without an
+/// object are calls of the own methods.
+fn get_method_callers(ast: &[AST], vars: &InScope) -> HashMap<(String, String), Vec<Option<String>>> {
+  let mut callers: HashMap<(String, String), Vec<Option<String>>> = HashMap::default();
+
+  for file in ast.iter() {
+    let globals = get_variable_types(&file.children);
+
+    for node in file.children.iter() {
+      let (owner, functions): (Option<String>, Vec<&AST>) = match &node.kind {
+        Kind::Function(fun) => (fun.in_external_namespace.clone(), vec![node]),
+        Kind::Class(_) => (Some(node.name.clone()), node.children.iter().filter(|child| matches!(child.kind, Kind::Function(_))).collect()),
+        _ => continue,
+      };
+      let attributes = owner.as_ref()
+        .and_then(|class_name| vars.classes.get(class_name))
+        .map(|class| &class.attributes);
+
+      for function in functions {
+        for (call, class_name) in get_method_calls(function, attributes, &globals, vars) {
+          callers.entry((class_name, call.name.clone())).or_default().push(owner.clone());
+        }
+        let Some(owner) = &owner else {
+          continue;
+        };
+        for call in function.children.iter().filter(|child| child.kind == Kind::Reference(Reference::Call) && child.dependencies.is_empty()) {
+          callers.entry((owner.clone(), call.name.clone())).or_default().push(Some(owner.clone()));
+        }
+      }
+    }
+  }
+
+  callers
+}
+
+/// Method definitions outside of the class declaration (`MyClass::foo`)
+fn get_out_of_line_definitions(ast: &[AST]) -> HashMap<(String, String), &AST> {
+  let mut definitions = HashMap::default();
+
+  for file in ast.iter() {
+    for node in file.children.iter() {
+      if let Kind::Function(Function { in_external_namespace: Some(class_name), .. }) = &node.kind {
+        definitions.insert((class_name.clone(), node.name.clone()), node);
+      }
+    }
+  }
+
+  definitions
+}
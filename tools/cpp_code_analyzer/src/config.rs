@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::ast::Severity;
+use crate::checker::{Config, PathConfig, RuleSetting};
+use crate::rules::{find_rule, ANALYZER_FINDINGS};
+
+pub const CONFIG_FILE_NAME: &str = ".cpp_analyzer.toml";
+
+/// Configuration of the whole project (found by walking up from the input)
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct ProjectFile {
+  abstract_prefix: Option<String>,
+  #[serde(default)]
+  entry_points: Vec<String>,
+  forbidden_terms: Option<Vec<String>>,
+  #[serde(default)]
+  factory_files: Vec<String>,
+  #[serde(default)]
+  include: Vec<String>,
+  #[serde(default)]
+  exclude: Vec<String>,
+  #[serde(default)]
+  rules: HashMap<String, String>,
+}
+
+/// Settings that subdirectories can override with their own file
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct PathFile {
+  #[serde(default)]
+  include: Vec<String>,
+  #[serde(default)]
+  exclude: Vec<String>,
+  #[serde(default)]
+  rules: HashMap<String, String>,
+}
+
+/// The outermost configuration file of `input` (or one of its parent
+/// directories) configures the project. Configuration files below it
+/// override the settings for their directory.
+pub fn load_config(input: &Path) -> Result<Config, String> {
+  let mut config = Config::default();
+  let input_dir = if input.is_dir() { input.to_path_buf() } else { input.parent().unwrap_or(Path::new("")).to_path_buf() };
+
+  let config_files = find_config_files(&input_dir)?;
+  for (idx, (config_dir, glob_base)) in config_files.into_iter().rev().enumerate() {
+    let path = config_dir.join(CONFIG_FILE_NAME);
+    let content = read_file(&path)?;
+    let settings = if idx == 0 {
+      let project = parse_project_config(&content).map_err(|err| format!("{}: {err}", path.display()))?;
+      if let Some(prefix) = project.abstract_prefix {
+        config.abstract_prefix = prefix;
+      }
+      if let Some(terms) = project.forbidden_terms {
+        config.forbidden_terms = terms;
+      }
+      config.entry_points = project.entry_points;
+      config.factory_files = project.factory_files;
+      PathFile {
+        include: project.include,
+        exclude: project.exclude,
+        rules: project.rules,
+      }
+    } else {
+      parse_override_config(&content).map_err(|err| format!("{}: {err}", path.display()))?
+    };
+    let depth = config_dir.components().count();
+    config.paths.push(to_path_config(settings, input_dir.clone(), glob_base, depth).map_err(|err| format!("{}: {err}", path.display()))?);
+  }
+
+  if input.is_dir() {
+    collect_override_files(input, &mut config.paths)?;
+  }
+
+  Ok(config)
+}
+
+/// Directories of the configuration files in `input_dir` and its parents
+/// (innermost first) together with the path of `input_dir` relative to
+/// them
+fn find_config_files(input_dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+  let dir = if input_dir.as_os_str().is_empty() { Path::new(".") } else { input_dir };
+  let absolute = std::path::absolute(dir).map_err(|err| format!("{}: {err}", dir.display()))?;
+
+  let mut config_files = vec![];
+  let mut relative = vec![];
+  for dir in absolute.ancestors() {
+    if dir.join(CONFIG_FILE_NAME).is_file() {
+      let glob_base = relative.iter().rev().collect();
+      config_files.push((dir.to_path_buf(), glob_base));
+    }
+    if let Some(name) = dir.file_name() {
+      relative.push(name.to_os_string());
+    }
+  }
+
+  Ok(config_files)
+}
+
+fn collect_override_files(dir: &Path, paths: &mut Vec<PathConfig>) -> Result<(), String> {
+  let entries = fs::read_dir(dir).map_err(|err| format!("{}: {err}", dir.display()))?;
+  for entry in entries {
+    let path = entry.map_err(|err| format!("{}: {err}", dir.display()))?.path();
+    let is_hidden = path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.'));
+    if path.is_dir() && !is_hidden {
+      let file = path.join(CONFIG_FILE_NAME);
+      if file.is_file() {
+        let settings = parse_override_config(&read_file(&file)?).map_err(|err| format!("{}: {err}", file.display()))?;
+        let depth = std::path::absolute(&path).map_err(|err| format!("{}: {err}", path.display()))?.components().count();
+        paths.push(to_path_config(settings, path.clone(), PathBuf::new(), depth).map_err(|err| format!("{}: {err}", file.display()))?);
+      }
+      collect_override_files(&path, paths)?;
+    }
+  }
+
+  Ok(())
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+  fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+fn parse_project_config(content: &str) -> Result<ProjectFile, String> {
+  toml::from_str(content).map_err(|err| err.to_string())
+}
+
+fn parse_override_config(content: &str) -> Result<PathFile, String> {
+  toml::from_str(content).map_err(|err| err.to_string())
+}
+
+fn to_path_config(settings: PathFile, directory: PathBuf, glob_base: PathBuf, depth: usize) -> Result<PathConfig, String> {
+  let to_patterns = |globs: Vec<String>| -> Result<Vec<glob::Pattern>, String> {
+    globs.iter().map(|glob| glob::Pattern::new(glob).map_err(|err| format!("invalid glob '{glob}': {err}"))).collect()
+  };
+
+  let mut rules = HashMap::new();
+  for (id, setting) in settings.rules {
+    if find_rule(&id).is_none() && !ANALYZER_FINDINGS.contains(&id.as_str()) {
+      return Err(format!("unknown rule '{id}'"));
+    }
+    rules.insert(id, parse_rule_setting(&setting)?);
+  }
+
+  Ok(PathConfig {
+    directory,
+    glob_base,
+    depth,
+    include: to_patterns(settings.include)?,
+    exclude: to_patterns(settings.exclude)?,
+    rules,
+  })
+}
+
+fn parse_rule_setting(setting: &str) -> Result<RuleSetting, String> {
+  match setting {
+    "off" => Ok(RuleSetting::Off),
+    "on" => Ok(RuleSetting::On),
+    "error" => Ok(RuleSetting::Severity(Severity::Error)),
+    "warning" => Ok(RuleSetting::Severity(Severity::Warning)),
+    "note" => Ok(RuleSetting::Severity(Severity::Note)),
+    _ => Err(format!("unknown rule setting '{setting}' (expected off, on, error, warning or note)")),
+  }
+}
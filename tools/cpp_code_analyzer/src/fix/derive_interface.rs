@@ -1,7 +1,7 @@
 use tree_sitter::{Node, Parser};
 use crate::ast::AST;
 
-pub fn modify_to_derive_from_interface(class: &AST, content: &str) -> String {
+pub fn modify_to_derive_from_interface(class: &AST, content: &str, interface_name: &str) -> String {
   let mut parser = Parser::new();
   parser.set_language(&tree_sitter_cpp::LANGUAGE.into()).expect("Error loading Cpp grammar");
 
@@ -15,10 +15,10 @@ pub fn modify_to_derive_from_interface(class: &AST, content: &str) -> String {
   let pos = find_derive_position(&node);
 
   let mut content = content.to_string();
-  content.insert_str(pos + offset, &format!(": public Abstract{}", class.name));
+  content.insert_str(pos + offset, &format!(": public {interface_name}"));
 
   let pos = find_include_position(&tree.root_node());
-  content.insert_str(pos, &(format!(r#"#include "Abstract{}.h""#, class.name) + "\n"));
+  content.insert_str(pos, &(format!(r#"#include "{interface_name}.h""#) + "\n"));
   content
 }
 
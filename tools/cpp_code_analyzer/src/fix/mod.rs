@@ -15,7 +15,7 @@ pub enum FixInstruction {
   CreateAbstractClass(String),
 }
 
-pub fn apply_fixes(fixes: Vec<Fix>, files: SourceFiles) -> SourceFiles {
+pub fn apply_fixes(fixes: Vec<Fix>, files: SourceFiles, abstract_prefix: &str) -> SourceFiles {
   let mut files = SourceAstTree::from_sources(files);
   for fix in fixes.into_iter() {
     use FixInstruction::*;
@@ -28,12 +28,13 @@ pub fn apply_fixes(fixes: Vec<Fix>, files: SourceFiles) -> SourceFiles {
 
         let idx = ast.children.iter().position(|element| element.name == class_name).expect("Not found");
         let class = &ast.children[idx];
-        let interface_content = create_interface_content(class, &content);
-        let interface_path = path.replace(&class_name, &format!("Abstract{class_name}"));
+        let interface_name = format!("{abstract_prefix}{class_name}");
+        let interface_content = create_interface_content(class, &content, &interface_name);
+        let interface_path = path.replace(&class_name, &interface_name);
         let interface_ast = AST::default().set_file_content(interface_content);
         files.tree.insert(interface_path, interface_ast);
 
-        let content = modify_to_derive_from_interface(class, &content);
+        let content = modify_to_derive_from_interface(class, &content, &interface_name);
         files.tree.insert(path, ast.set_file_content(content));
       }
     }
@@ -44,10 +45,10 @@ pub fn apply_fixes(fixes: Vec<Fix>, files: SourceFiles) -> SourceFiles {
     .collect()
 }
 
-fn create_interface_content(class: &AST, context_content: &str) -> String {
-  let mut content = "\nclass Abstract".to_string() + &class.name;
+fn create_interface_content(class: &AST, context_content: &str, interface_name: &str) -> String {
+  let mut content = format!("\nclass {interface_name}");
   content += " {\npublic:\n";
-  content += &format!("  virtual ~Abstract{}() = default;\n\n", class.name);
+  content += &format!("  virtual ~{interface_name}() = default;\n\n");
   for child in class.children.iter() {
     match &child.kind {
       Kind::Function(fun) => {
@@ -1,6 +1,8 @@
 pub mod parser;
 pub mod checker;
+pub mod config;
 pub mod ast;
+pub mod rules;
 pub mod visualize;
 pub mod fix;
 
@@ -14,6 +16,18 @@ pub fn analyze_cpp_errors(name: &str, input: &str) -> Vec<ast::LintError> {
   checker::check_global_codechunk(vec![ast])
 }
 
+pub fn analyze_cpp_bytes(input: &[u8]) -> Vec<String> {
+  let ast = parser::parse_cpp_bytes("", input);
+
+  lints_to_strings(checker::check_global_codechunk(vec![ast]))
+}
+
+pub fn analyze_cpp_files(files: &[(&str, &str)]) -> Vec<String> {
+  let ast = files.iter().map(|(name, input)| parser::parse_cpp_chunc(name, input)).collect();
+
+  lints_to_strings(checker::check_global_codechunk(ast))
+}
+
 fn lints_to_strings(input: Vec<ast::LintError>) -> Vec<String> {
   input.into_iter().map(|err| format!("{}", err.kind)).collect()
 }
@@ -1,28 +1,51 @@
 use std::collections::HashMap;
 use std::{fs, io};
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::files::SimpleFiles;
 use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
 use codespan_reporting::term;
-use cpp_code_analyzer::ast::{Kind, AST};
+use cpp_code_analyzer::ast::{Kind, Severity, AST};
 use cpp_code_analyzer::visualize::{to_graphml, to_graphviz, visualize};
-use cpp_code_analyzer::{checker, parser};
+use cpp_code_analyzer::{checker, config, parser, rules};
 
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// File to check
-    #[arg(short, long, value_name = "FILE")]
-    input: PathBuf,
+    #[arg(short, long, value_name = "FILE", required = true)]
+    input: Option<PathBuf>,
     /// Output format
     #[arg(short, long, value_enum, default_value_t=OutputType::Terminal)]
     format: OutputType,
     /// Fix problems interactivly
     #[arg(long, default_value_t=false)]
     interactive: bool,
+    /// Comma separated terms that must not be used in abstract interfaces
+    /// (replaces the default list of bus protocols and chip names)
+    #[arg(long, value_name = "TERMS", value_delimiter = ',')]
+    forbidden_terms: Option<Vec<String>>,
+    /// Comma separated files that may instantiate concrete classes (in
+    /// addition to the file with `main` or `setup`)
+    #[arg(long, value_name = "FILES", value_delimiter = ',')]
+    factory_files: Vec<String>,
+    /// Fail (non-zero exit code) on warnings as well as on errors
+    #[arg(long, default_value_t=false)]
+    deny_warnings: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the requirement and its rationale from the styleguide
+    Explain {
+      /// Rule ID (e.g. E_MODULES_NO_INIT_FUNCTION) or styleguide anchor
+      rule: String,
+    },
 }
 
 #[derive(ValueEnum, Clone, Copy)]
@@ -33,27 +56,66 @@ enum OutputType {
   /// Graphviz dot format
   Dot,
   Graphml,
+  /// List all `lint: ignore` suppressions for review
+  Suppressions,
 }
 
-fn main() -> io::Result<()> {
+fn main() -> io::Result<ExitCode> {
     env_logger::init();
     let args = Args::parse();
 
-    let entries = get_sources_from_dir(&args.input)?;
+    if let Some(Command::Explain { rule }) = &args.command {
+      match rules::explain(rule) {
+        Some(explanation) => print!("{explanation}"),
+        None => {
+          eprintln!("error: unknown rule '{rule}'");
+          return Ok(ExitCode::FAILURE);
+        }
+      }
+      return Ok(ExitCode::SUCCESS);
+    }
+
+    let input = args.input.expect("input is required without subcommand");
+    let mut config = match config::load_config(&input) {
+      Ok(config) => config,
+      Err(err) => {
+        eprintln!("error: {err}");
+        return Ok(ExitCode::FAILURE);
+      }
+    };
+    if let Some(terms) = args.forbidden_terms {
+      config.forbidden_terms = terms;
+    }
+    config.factory_files.extend(args.factory_files);
+    config.today = Some(checker::current_date());
+    let mut entries = get_sources_from_dir(&input)?;
+    entries.retain(|file| config.is_included(&file.name));
+    let entries = checker::mark_abstract_classes(entries, &config.abstract_prefix);
 
     use  OutputType::*;
     match args.format {
-      Terminal => print_all_errors(entries, args.interactive),
+      Terminal => {
+        let (errors, warnings) = print_all_errors(entries, &config, args.interactive);
+        if errors > 0 || (args.deny_warnings && warnings > 0) {
+          return Ok(ExitCode::FAILURE);
+        }
+      }
       Svg => to_svg(entries),
       Dot => to_dot(entries),
       Graphml => {
         println!("{}", to_graphml(entries, ""));
       }
+      Suppressions => {
+        for entry in checker::report_lint_instructions(&entries) {
+          println!("{entry}");
+        }
+      }
     }
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
-fn print_all_errors(ast: Vec<AST>, fix_interactive: bool) {
+/// Returns the number of errors and warnings
+fn print_all_errors(ast: Vec<AST>, config: &checker::Config, fix_interactive: bool) -> (usize, usize) {
   let mut files = SimpleFiles::new();
   let mut mapping = HashMap::<String, usize>::default();
 
@@ -64,32 +126,52 @@ fn print_all_errors(ast: Vec<AST>, fix_interactive: bool) {
     }
   }
 
-  let errors = checker::check_global_codechunk(ast);
+  let errors = checker::check_global_codechunk_with_config(ast, config);
 
   let writer = StandardStream::stderr(ColorChoice::Always);
-  let config = codespan_reporting::term::Config::default();
+  let term_config = codespan_reporting::term::Config::default();
 
   let mut user_input = String::new();
   for error in errors.iter() {
     let file_id = mapping.get(&error.file_path).unwrap_or(&0);
-    let diagnostic = Diagnostic::error()
+    let mut labels = vec![
+      Label::primary(*file_id, error.range.start..error.range.end),
+    ];
+    for label in error.secondary_labels.iter() {
+      let file_id = mapping.get(&label.file_path).unwrap_or(&0);
+      labels.push(Label::secondary(*file_id, label.range.start..label.range.end).with_message(&label.message));
+    }
+    let diagnostic = match config.get_severity(error).unwrap_or(Severity::Error) {
+      Severity::Error => Diagnostic::error(),
+      Severity::Warning => Diagnostic::warning(),
+      Severity::Note => Diagnostic::note(),
+    };
+    let mut notes = error.notes.clone();
+    if let Some(url) = error.kind.rule().and_then(|rule| rule.url()) {
+      notes.push(format!("see {url}"));
+    }
+    let diagnostic = diagnostic
+        .with_code(error.kind.id())
         .with_message(&format!("{}", error.kind))
-        .with_labels(vec![
-            Label::primary(*file_id, error.range.start..error.range.end),
-        ]);
+        .with_labels(labels)
+        .with_notes(notes);
     let diagnostic = if fix_interactive {
       diagnostic.with_note("no fix available. Hit enter to continue")
     } else {
       diagnostic
     };
 
-    term::emit(&mut writer.lock(), &config, &files, &diagnostic).unwrap();
+    term::emit(&mut writer.lock(), &term_config, &files, &diagnostic).unwrap();
     if fix_interactive {
       std::io::stdin().read_line(&mut user_input).unwrap();
     }
   }
 
-  println!("found {} errors", errors.len());
+  let count = |severity| errors.iter().filter(|error| config.get_severity(error) == Some(severity)).count();
+  let (error_count, warning_count) = (count(Severity::Error), count(Severity::Warning));
+  println!("found {} errors and {} warnings", error_count, warning_count);
+
+  (error_count, warning_count)
 }
 
 fn to_svg(ast: Vec<AST>) {
@@ -113,15 +195,15 @@ fn get_sources_from_dir(dir: &Path) -> io::Result<Vec<AST>> {
       } else {
         let filepath = path.to_string_lossy().to_string();
         if filepath.ends_with(".h") || filepath.ends_with(".cpp") {
-          let input = std::fs::read_to_string(&path)?;
-          entries.push(parser::parse_cpp_chunc(&filepath, &input));
+          let input = std::fs::read(&path)?;
+          entries.push(parser::parse_cpp_bytes(&filepath, &input));
         }
       }
     }
   } else {
     let filepath = dir.to_string_lossy().to_string();
-    let input = std::fs::read_to_string(&dir)?;
-    entries.push(parser::parse_cpp_chunc(&filepath, &input));
+    let input = std::fs::read(&dir)?;
+    entries.push(parser::parse_cpp_bytes(&filepath, &input));
   }
 
   Ok(entries)
@@ -1,4 +1,4 @@
-use crate::ast::{Class, Function, Kind, LintInstruction, LintErrorTypes, Reference, Variable, AST};
+use crate::ast::{Class, Declarator, Function, Kind, LintInstruction, LintErrorTypes, Macro, Reference, StorageClass, Variable, AST, DEFAULT_ABSTRACT_PREFIX};
 use tree_sitter::{Node, Parser};
 
 pub fn parse_cpp_chunc(name: &str, input: &str) -> AST {
@@ -15,18 +15,43 @@ pub fn parse_cpp_chunc(name: &str, input: &str) -> AST {
     ..AST::default()
   };
   parse_global_codechunk(&mut base, &root_node, input);
+  find_lint_instructions(&root_node, input, &mut base);
 
   base
 }
 
+/// Files that are not valid UTF-8 are still parsed (with invalid bytes
+/// replaced) so that the remaining lints can be reported as well
+pub fn parse_cpp_bytes(name: &str, input: &[u8]) -> AST {
+  match std::str::from_utf8(input) {
+    Ok(input) => parse_cpp_chunc(name, input),
+    Err(err) => {
+      let input = String::from_utf8_lossy(input);
+      let mut base = parse_cpp_chunc(name, &input);
+      let start = err.valid_up_to();
+      base.children.insert(0, AST {
+        kind: Kind::LintError(LintErrorTypes::GeneralUtf8Encoding(start)),
+        range: start..start + char::REPLACEMENT_CHARACTER.len_utf8(),
+        ..AST::default()
+      });
+      base
+    }
+  }
+}
+
 fn parse_global_codechunk(base: &mut AST, cl: &Node, code: &str) {
   for idx in 0..cl.child_count() as u32 {
     let child = cl.child(idx).unwrap();
     match child.kind() {
       "class_specifier" => base.children.push(extract_class(&child, code)),
       "declaration" => base.children.append(&mut extract_declaration(&child, code, "public")),
-      "preproc_ifdef"|"preproc_def"|"namespace_definition"
-        |"declaration_list"|"preproc_if"|"preproc_elif"
+      "preproc_ifdef"|"preproc_if" => {
+        base.children.push(extract_conditional(&child, code));
+        parse_global_codechunk(base, &child, code);
+      }
+      "preproc_def"|"preproc_function_def" => base.children.push(parse_macro(&child, code)),
+      "namespace_definition"
+        |"declaration_list"|"preproc_elif"
         |"preproc_else"|"binary_expression"|"unary_expression" => parse_global_codechunk(base, &child, code),
       "preproc_include" => base.dependencies.push(parse_include(&child, code)),
       "identifier"|"namespace_identifier" => (), // ignoring identifiers on global level
@@ -39,7 +64,10 @@ fn parse_global_codechunk(base: &mut AST, cl: &Node, code: &str) {
       x if is_read_operator(x) => (),
       x if is_literal(x) => (),
       "enum_specifier" => base.children.append(&mut parse_enum(&child, code)),
-      "type_definition" => parse_global_codechunk(base, &child, code),
+      "type_definition" => {
+        parse_global_codechunk(base, &child, code);
+        name_type_definition(base, &child, code);
+      }
       "struct_specifier" => base.children.push(parse_struct(&child, code)),
       "alias_declaration" => base.children.push(parse_alias(&child, code)),
       "type_identifier" => (),
@@ -53,6 +81,121 @@ fn parse_global_codechunk(base: &mut AST, cl: &Node, code: &str) {
   }
 }
 
+/// `typedef struct { ... } name;` only gets its name from the declarator
+fn name_type_definition(base: &mut AST, node: &Node, code: &str) {
+  let Some(declarator) = node.child_by_field_name("declarator").filter(|declarator| declarator.kind() == "type_identifier") else {
+    return;
+  };
+  let range = declarator.byte_range();
+  let name = &code[range.start..range.end];
+
+  match base.children.last_mut() {
+    Some(last) if last.kind == Kind::Type && last.name.is_empty() => last.name = name.to_string(),
+    Some(last) if last.kind == Kind::Type && last.name == name => (),
+    _ => base.children.push(AST {
+      name: name.to_string(),
+      kind: Kind::Type,
+      range: node.byte_range(),
+      ..AST::default()
+    }),
+  }
+}
+
+fn parse_macro(node: &Node, code: &str) -> AST {
+  let text = |node: Node| {
+    let range = node.byte_range();
+    &code[range.start..range.end]
+  };
+  let name = node.child_by_field_name("name").map(text).unwrap_or_default();
+  let params: Vec<&str> = node.child_by_field_name("parameters")
+    .map(|params| (0..params.named_child_count() as u32).filter_map(|idx| params.named_child(idx)).map(text).collect())
+    .unwrap_or_default();
+  let value = node.child_by_field_name("value");
+  // the directive also contains the line break
+  let range = node.byte_range();
+  let end = range.start + code[range.clone()].trim_end().len();
+
+  AST {
+    name: name.to_string(),
+    kind: Kind::Macro(Macro {
+      is_function_like: node.kind() == "preproc_function_def",
+      value: value.map(|value| text(value).trim().to_string()),
+    }),
+    children: value.map(|value| extract_macro_references(&value, code, &params)).unwrap_or_default(),
+    range: range.start..end,
+    ..AST::default()
+  }
+}
+
+/// Keywords that can show up in macro bodies
+const MACRO_KEYWORDS: [&str; 14] = [
+  "do", "while", "if", "else", "for", "return", "sizeof", "true", "false",
+  "nullptr", "const", "static_cast", "break", "continue",
+];
+
+/// The body of a macro is not parsed by tree-sitter. Identifiers are
+/// picked from its tokens, assignments and increments make them writes.
+fn extract_macro_references(value: &Node, code: &str, params: &[&str]) -> Vec<AST> {
+  let mut children = vec![];
+  let offset = value.start_byte();
+  let body = &code[value.byte_range()];
+  let bytes = body.as_bytes();
+
+  let mut idx = 0;
+  while idx < bytes.len() {
+    let c = bytes[idx];
+    if c == b'"' || c == b'\'' {
+      idx += 1;
+      while idx < bytes.len() && bytes[idx] != c {
+        idx += if bytes[idx] == b'\\' { 2 } else { 1 };
+      }
+      idx += 1;
+    } else if c.is_ascii_digit() {
+      while idx < bytes.len() && (bytes[idx].is_ascii_alphanumeric() || bytes[idx] == b'.') {
+        idx += 1;
+      }
+    } else if c.is_ascii_alphabetic() || c == b'_' {
+      let start = idx;
+      while idx < bytes.len() && (bytes[idx].is_ascii_alphanumeric() || bytes[idx] == b'_') {
+        idx += 1;
+      }
+      let name = &body[start..idx];
+      let before = body[..start].trim_end();
+      let after = body[idx..].trim_start();
+      let is_member = before.ends_with('.') || before.ends_with("->");
+      if is_member || params.contains(&name) || MACRO_KEYWORDS.contains(&name) {
+        continue;
+      }
+      let kind = if after.starts_with('(') {
+        Reference::Call
+      } else if is_macro_write(before, after) {
+        Reference::Write
+      } else {
+        Reference::Read
+      };
+      children.push(AST {
+        name: name.to_string(),
+        kind: Kind::Reference(kind),
+        range: offset + start..offset + idx,
+        ..AST::default()
+      });
+    } else {
+      idx += 1;
+    }
+  }
+
+  children
+}
+
+fn is_macro_write(before: &str, after: &str) -> bool {
+  const ASSIGNMENTS: [&str; 10] = ["+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<<=", ">>="];
+  let is_assignment = after.starts_with('=') && !after.starts_with("==");
+  is_assignment
+    || ASSIGNMENTS.iter().any(|op| after.starts_with(op))
+    || after.starts_with("++") || after.starts_with("--")
+    || before.ends_with("++") || before.ends_with("--")
+}
+
 fn parse_include(node: &Node, code: &str) -> AST {
   let mut children = vec![];
   let mut name = "";
@@ -73,23 +216,24 @@ fn parse_include(node: &Node, code: &str) -> AST {
     }
   }
 
+  // the include directive also contains the line break
+  let range = node.byte_range();
+  let end = range.start + code[range.clone()].trim_end().len();
+
   AST {
     name: name.to_string(),
     kind: Kind::Reference(Reference::Depend),
     children,
-    range: node.byte_range(),
+    range: range.start..end,
     ..AST::default()
   }
 }
 
 fn extract_class(cl: &Node, code: &str) -> AST {
   let name = get_class_name(cl, code);
-  let is_abstract = name.starts_with("Abstract");
+  let is_abstract = name.starts_with(DEFAULT_ABSTRACT_PREFIX);
   let mut dependencies = vec![];
   let mut children = vec![];
-  let mut instructions = vec![];
-
-  find_lint_instructions(cl, code, &mut instructions, &mut children);
 
   for idx in 0..cl.child_count() as u32 {
     let child = cl.child(idx).unwrap();
@@ -120,53 +264,135 @@ fn extract_class(cl: &Node, code: &str) -> AST {
     children,
     dependencies,
     range: cl.byte_range(),
-    instructions,
-  }
-}
-
-fn find_lint_instructions(node: &Node, code: &str, instructions: &mut Vec<LintInstruction>, children: &mut Vec<AST>) {
-  if let Some(before) = node.prev_sibling() {
-    if before.kind() == "comment" {
-      let range = before.byte_range();
-      let previous_comment = &code[range.start..range.end];
-      let mut next_is_instruction = false;
-      const LINT_PATTERN: &str = "lint: ignore ";
-      for instruction in previous_comment.split_inclusive(LINT_PATTERN) {
-        if next_is_instruction {
-          match instruction.split_once(" ") {
-            Some((number, reason)) => instructions.push(LintInstruction {
-              ident: number.to_string(),
-              reason: reason.to_string(),
-            }),
-            None => children.push(AST {
-              kind: Kind::LintError(LintErrorTypes::LintInstructionNotParseble(previous_comment.to_string())),
-              range: range.clone(),
-              ..AST::default()
-            })
-          }
-        }
-        next_is_instruction = instruction.ends_with(LINT_PATTERN);
+    ..AST::default()
+  }
+}
+
+/// Collects the `lint: ignore` comments of the whole file. Their scope is
+/// the following declaration or statement, their own line when they trail
+/// code and the whole file for `lint: ignore-file`.
+fn find_lint_instructions(node: &Node, code: &str, base: &mut AST) {
+  for idx in 0..node.child_count() as u32 {
+    let child = node.child(idx).unwrap();
+    if child.kind() == "comment" {
+      parse_lint_instructions(&child, code, base);
+    } else {
+      find_lint_instructions(&child, code, base);
+    }
+  }
+}
+
+fn parse_lint_instructions(comment: &Node, code: &str, base: &mut AST) {
+  const LINT_PATTERN: &str = "lint: ignore";
+  let range = comment.byte_range();
+  let text = &code[range.start..range.end];
+
+  let starts: Vec<usize> = text.match_indices(LINT_PATTERN).map(|(idx, _)| idx + LINT_PATTERN.len()).collect();
+  for (idx, start) in starts.iter().enumerate() {
+    let end = starts.get(idx + 1).map(|next| next - LINT_PATTERN.len()).unwrap_or(text.len());
+    let instruction = &text[*start..end];
+    let (whole_file, instruction) = match instruction.strip_prefix("-file") {
+      Some(rest) => (true, rest),
+      None => (false, instruction),
+    };
+    if !instruction.starts_with(char::is_whitespace) {
+      continue;
+    }
+
+    let instruction = instruction.trim().trim_end_matches("*/").trim();
+    let (ident, reason) = instruction.split_once(char::is_whitespace).unwrap_or((instruction, ""));
+    let mut until = None;
+    let mut ticket = None;
+    let mut words = vec![];
+    for word in reason.split_whitespace() {
+      if let Some(date) = word.strip_prefix("until=") {
+        until = Some(date.to_string());
+      } else if let Some(reference) = word.strip_prefix("ticket=") {
+        ticket = Some(reference.to_string());
+      } else {
+        words.push(word);
       }
     }
+    if ident.is_empty() || until.as_deref().is_some_and(|date| !is_iso_date(date)) {
+      base.children.push(AST {
+        kind: Kind::LintError(LintErrorTypes::LintInstructionNotParseble(text.to_string())),
+        range: range.clone(),
+        ..AST::default()
+      });
+      continue;
+    }
+
+    let scope = if whole_file {
+      0..code.len()
+    } else {
+      get_lint_instruction_scope(comment, code)
+    };
+    base.instructions.push(LintInstruction {
+      ident: ident.to_string(),
+      reason: words.join(" "),
+      until,
+      ticket,
+      range: range.clone(),
+      scope,
+    });
+  }
+}
+
+/// Dates of lint instructions are written as `YYYY-MM-DD`
+fn is_iso_date(date: &str) -> bool {
+  date.len() == 10 && date.char_indices().all(|(idx, c)| match idx {
+    4|7 => c == '-',
+    _ => c.is_ascii_digit(),
+  })
+}
+
+fn get_lint_instruction_scope(comment: &Node, code: &str) -> core::ops::Range<usize> {
+  let range = comment.byte_range();
+  let line_start = code[..range.start].rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+  if !code[line_start..range.start].trim().is_empty() {
+    return line_start..range.end;
+  }
+
+  let mut next = comment.next_sibling();
+  while let Some(sibling) = next.filter(|sibling| sibling.kind() == "comment") {
+    next = sibling.next_sibling();
+  }
+  match next {
+    Some(sibling) => range.start..sibling.end_byte(),
+    None => range,
   }
 }
 
 fn extract_class_fields(fields: &Node, code: &str) -> Vec<AST> {
   let mut children = vec![];
-
   let mut access_specifier = "public";
+  extract_fields(fields, code, &mut access_specifier, &mut children);
+
+  children
+}
+
+/// Access specifiers inside of conditional compilation blocks also apply
+/// to the fields following the block
+fn extract_fields<'a>(fields: &Node, code: &'a str, access_specifier: &mut &'a str, children: &mut Vec<AST>) {
   for idx in 0..fields.child_count() as u32 {
     let child = fields.child(idx).unwrap();
     let range = child.byte_range();
     match child.kind() {
+      _ if is_conditional_directive(fields, idx) => (),
       "access_specifier" => {
-        access_specifier = &code[range.start..range.end];
+        *access_specifier = &code[range.start..range.end];
       }
+      "preproc_ifdef"|"preproc_if" => {
+        children.push(extract_conditional(&child, code));
+        extract_fields(&child, code, access_specifier, children);
+      }
+      "preproc_elif"|"preproc_else" => extract_fields(&child, code, access_specifier, children),
       "declaration"|"field_declaration" => children.append(&mut extract_declaration(&child, code, access_specifier)),
       "function_definition" => children.push(extract_function(&child, code, access_specifier)),
       "type_definition" => children.push(parse_struct(&child, code)),
       "type_identifier"|"comment"|";"|"{"|"}"|"("|")"|":" => (),
       "alias_declaration" => children.push(parse_alias(&child, code)),
+      "friend_declaration" => children.push(extract_friend(&child, code)),
       _ => children.push(AST {
         kind: Kind::Unhandled(format!("extract_class_fields: {}", child.to_sexp())),
         range,
@@ -174,8 +400,50 @@ fn extract_class_fields(fields: &Node, code: &str) -> Vec<AST> {
       }),
     }
   }
+}
 
-  children
+fn extract_friend(node: &Node, code: &str) -> AST {
+  let mut name = String::new();
+
+  for idx in 0..node.child_count() as u32 {
+    let child = node.child(idx).unwrap();
+    let range = child.byte_range();
+    match child.kind() {
+      "type_identifier"|"qualified_identifier"|"template_type" => name = code[range.start..range.end].to_string(),
+      "declaration" => name = get_function_name(&child, code).0,
+      _ => (),
+    }
+  }
+
+  AST {
+    name,
+    kind: Kind::Friend,
+    range: node.byte_range(),
+    ..AST::default()
+  }
+}
+
+/// Conditional compilation blocks are kept as node next to the code they
+/// wrap. The wrapped code itself is parsed as if there was no directive.
+fn extract_conditional(node: &Node, code: &str) -> AST {
+  let range = node.byte_range();
+  let directive = code[range.start..range.end].lines().next().unwrap_or_default();
+
+  AST {
+    name: directive.trim().to_string(),
+    kind: Kind::Conditional,
+    range,
+    ..AST::default()
+  }
+}
+
+/// Whether the child is part of the directive (`#ifdef NAME`, `#endif`, ...)
+/// instead of the code wrapped by the conditional compilation block
+fn is_conditional_directive(node: &Node, idx: u32) -> bool {
+  node.kind().starts_with("preproc_")
+    && (matches!(node.field_name_for_child(idx), Some("name"|"condition"))
+      || matches!(node.child(idx).unwrap().kind(), "\n" | "preproc_defined")
+      || node.child(idx).unwrap().kind().starts_with('#'))
 }
 
 fn extract_derives(fields: &Node, code: &str, class_name: &str) -> (Vec<AST>, Vec<AST>) {
@@ -266,14 +534,33 @@ fn extract_declaration(field: &Node, code: &str, access_specifier: &str) -> Vec<
     let child = field.child(idx).unwrap();
     let range = child.byte_range();
     match child.kind() {
+      "qualified_identifier" if field.field_name_for_child(idx) == Some("type") => {
+        children.push(AST {
+          name: code[range.start..range.end].to_string(),
+          kind: Kind::Reference(Reference::TypeRead),
+          range,
+          ..AST::default()
+        });
+      }
+      "identifier"|"qualified_identifier" if field.field_name_for_child(idx) == Some("value") => {
+        children.push(AST {
+          name: code[range.start..range.end].to_string(),
+          kind: Kind::Reference(Reference::Read),
+          range,
+          ..AST::default()
+        });
+      }
       "identifier"|"array_declarator"|"field_identifier"
-        |"qualified_identifier"|"reference_declarator" => {
+        |"qualified_identifier" => {
         children.push(AST {
           name: get_variable_name(&child, code),
           kind: Kind::Variable(Variable {
             visibility: access_specifier.to_string(),
-            is_const: check_is_const(&field.parent().unwrap(), code),
+            is_const: check_is_const(field, &child, code),
+            storage: get_storage_class(field, code),
+            declarator: Declarator::Value,
           }),
+          dependencies: get_declared_type(field, code),
           range,
           ..AST::default()
         });
@@ -281,31 +568,24 @@ fn extract_declaration(field: &Node, code: &str, access_specifier: &str) -> Vec<
       "init_declarator" => {
         children.append(&mut extract_declaration(&child, code, access_specifier));
       },
-      "pointer_declarator" => {
+      "pointer_declarator"|"reference_declarator" => {
         let is_function = code[range.start..range.end].contains("(");
-        let name = if is_function {
-          let (name, _namespace) = get_function_name(&child, code);
-          name
+        if is_function {
+          children.push(extract_function(&field, code, access_specifier));
         } else {
-          get_variable_name(&child, code)
-        };
-        children.push(AST {
-          name: name.to_string(),
-          kind: if is_function {
-            Kind::Function(Function {
+          children.push(AST {
+            name: get_variable_name(&child, code),
+            kind: Kind::Variable(Variable {
               visibility: access_specifier.to_string(),
-              is_virtual: check_pure_virtual(&field, code),
-              in_external_namespace: None,
-            })
-          } else {
-            Kind::Variable(Variable {
-              visibility: access_specifier.to_string(),
-              is_const: check_is_const(&field.parent().unwrap(), code),
-            })
-          },
-          range,
-          ..AST::default()
-        });
+              is_const: check_is_const(field, &child, code),
+              storage: get_storage_class(field, code),
+              declarator: get_declarator(&child, field, code),
+            }),
+            dependencies: get_declared_type(field, code),
+            range,
+            ..AST::default()
+          });
+        }
       }
       "function_declarator" => {
         children.push(extract_function(&field, code, access_specifier));
@@ -313,6 +593,18 @@ fn extract_declaration(field: &Node, code: &str, access_specifier: &str) -> Vec<
       "enum_specifier" => {
         children.append(&mut parse_enum(&child, code));
       }
+      "struct_specifier" if child.child_by_field_name("body").is_some() => {
+        children.push(parse_struct(&child, code));
+      }
+      "struct_specifier" => {
+        let name = child.child_by_field_name("name").unwrap_or(child).byte_range();
+        children.push(AST {
+          name: code[name.start..name.end].to_string(),
+          kind: Kind::Reference(Reference::TypeRead),
+          range,
+          ..AST::default()
+        });
+      }
       ";"|"{"|"}"|"("|")"|":"|"="|","|"*" => (),
       x if is_primitive_type(x) => (),
       "placeholder_type_specifier"
@@ -330,6 +622,7 @@ fn extract_declaration(field: &Node, code: &str, access_specifier: &str) -> Vec<
       "initializer_list" => (),
       x if is_statement(x) => children.append(&mut extract_statement(&child, code)),
       x if is_update_expression(x) => children.append(&mut extract_update_expression(&child, code)),
+      "new_expression" => children.append(&mut extract_new_expression(&child, code)),
       "call_expression" => children.append(&mut extract_call_expression(&child, code)),
       "argument_list" => children.append(&mut extract_arguments(&child, code)),
       "field_expression" => children.append(&mut extract_field_expression(&child, code)),
@@ -344,10 +637,35 @@ fn extract_declaration(field: &Node, code: &str, access_specifier: &str) -> Vec<
   children
 }
 
+/// Type of the variables declared in a declaration (the type of an
+/// `init_declarator` is found at the surrounding declaration)
+fn get_declared_type(declaration: &Node, code: &str) -> Vec<AST> {
+  let declaration = match declaration.kind() {
+    "init_declarator" => declaration.parent().unwrap(),
+    _ => *declaration,
+  };
+
+  match declaration.child_by_field_name("type") {
+    Some(type_node) => {
+      let range = type_node.byte_range();
+      vec![AST {
+        name: code[range.start..range.end].to_string(),
+        kind: Kind::Reference(Reference::TypeRead),
+        range,
+        ..AST::default()
+      }]
+    }
+    None => vec![],
+  }
+}
+
 fn extract_function(field: &Node, code: &str, access_specifier: &str) -> AST {
   let (name, namespace) = get_function_name(field, code);
   let mut dependencies = vec![];
   let mut children = vec![];
+  let mut returns = Declarator::Value;
+  let mut is_deleted = false;
+  let mut is_defaulted = false;
 
   for idx in 0..field.child_count() as u32 {
     let child = field.child(idx).unwrap();
@@ -358,15 +676,40 @@ fn extract_function(field: &Node, code: &str, access_specifier: &str) -> AST {
         ..AST::default()
       }),
       "function_declarator" => children.append(&mut extract_parameters(&child, code)),
-      "type_identifier"|";"|"comment" => (),
+      "type_identifier" => {
+        let range = child.byte_range();
+        dependencies.push(AST {
+          name: code[range.start..range.end].to_string(),
+          kind: Kind::Reference(Reference::TypeRead),
+          range,
+          ..AST::default()
+        })
+      }
+      ";"|"comment" => (),
       "compound_statement" => children.append(&mut extract_statement(&child, code)),
-      "template_type" => (),
-      "pointer_declarator" => (),
+      "template_type" => dependencies.append(&mut extract_template_type(&child, code)),
+      "qualified_identifier" => {
+        let range = child.byte_range();
+        dependencies.push(AST {
+          name: code[range.start..range.end].to_string(),
+          kind: Kind::Reference(Reference::TypeRead),
+          range,
+          ..AST::default()
+        })
+      }
+      "pointer_declarator"|"reference_declarator" => {
+        returns = get_declarator(&child, field, code);
+        if let Some(declarator) = find_function_declarator(&child) {
+          children.append(&mut extract_parameters(&declarator, code));
+        }
+      }
       x if is_literal(x) => (),
       "=" => (),
       "initializer_list" => (),
       "type_qualifier"|"storage_class_specifier" => (),
-      "virtual"|"default_method_clause" => (),
+      "virtual" => (),
+      "default_method_clause" => is_defaulted = true,
+      "delete_method_clause" => is_deleted = true,
       _ => children.push(AST {
         kind: Kind::Unhandled(format!("extract_function: {}", child.to_sexp())),
         range: child.byte_range(),
@@ -381,6 +724,10 @@ fn extract_function(field: &Node, code: &str, access_specifier: &str) -> AST {
       is_virtual: check_pure_virtual(&field, code),
       visibility: access_specifier.to_string(),
       in_external_namespace: namespace,
+      returns,
+      storage: get_storage_class(field, code),
+      is_deleted,
+      is_defaulted,
     }),
     children,
     dependencies,
@@ -389,6 +736,36 @@ fn extract_function(field: &Node, code: &str, access_specifier: &str) -> AST {
   }
 }
 
+/// The function declarator of functions returning pointers or references
+/// is nested inside of the pointer/reference declarator
+fn find_function_declarator<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+  for idx in 0..node.child_count() as u32 {
+    let child = node.child(idx).unwrap();
+    match child.kind() {
+      "function_declarator" => return Some(child),
+      "pointer_declarator"|"reference_declarator" => return find_function_declarator(&child),
+      _ => (),
+    }
+  }
+
+  None
+}
+
+/// Whether a declared type is used by value, by reference or as pointer
+fn get_declarator(declarator: &Node, declaration: &Node, code: &str) -> Declarator {
+  let is_const = (0..declaration.child_count() as u32).any(|idx| {
+    let child = declaration.child(idx).unwrap();
+    let range = child.byte_range();
+    child.kind() == "type_qualifier" && &code[range.start..range.end] == "const"
+  });
+
+  match declarator.kind() {
+    "reference_declarator" => Declarator::Reference { is_const },
+    "pointer_declarator" => Declarator::Pointer { is_const },
+    _ => Declarator::Value,
+  }
+}
+
 fn extract_statement(node: &Node, code: &str) -> Vec<AST> {
   let mut children = vec![];
 
@@ -396,6 +773,12 @@ fn extract_statement(node: &Node, code: &str) -> Vec<AST> {
     let child = node.child(idx).unwrap();
     let range = child.byte_range();
     match child.kind() {
+      _ if is_conditional_directive(node, idx) => (),
+      "preproc_ifdef"|"preproc_if" => {
+        children.push(extract_conditional(&child, code));
+        children.append(&mut extract_statement(&child, code));
+      }
+      "preproc_elif"|"preproc_else" => children.append(&mut extract_statement(&child, code)),
       x if is_statement(x)  => children.append(&mut extract_statement(&child, code)),
       "identifier"|"qualified_identifier" => children.push(AST {
         name: code[range.start..range.end].to_string(),
@@ -404,6 +787,7 @@ fn extract_statement(node: &Node, code: &str) -> Vec<AST> {
         ..AST::default()
       } ),
       x if is_update_expression(x) => children.append(&mut extract_update_expression(&child, code)),
+      "new_expression" => children.append(&mut extract_new_expression(&child, code)),
       "call_expression" => children.append(&mut extract_call_expression(&child, code)),
       "field_expression" => children.append(&mut extract_field_expression(&child, code)),
       "declaration" => children.append(&mut extract_declaration(&child, code, "public")),
@@ -427,6 +811,34 @@ fn extract_statement(node: &Node, code: &str) -> Vec<AST> {
   children
 }
 
+/// `new T(...)` instantiates its type, the arguments are read
+fn extract_new_expression(node: &Node, code: &str) -> Vec<AST> {
+  let mut children = vec![];
+
+  for idx in 0..node.child_count() as u32 {
+    let child = node.child(idx).unwrap();
+    let range = child.byte_range();
+    match child.kind() {
+      x if is_primitive_type(x) => (),
+      _ if node.field_name_for_child(idx) == Some("type") => children.push(AST {
+        name: code[range.start..range.end].to_string(),
+        kind: Kind::Reference(Reference::Instantiate),
+        range,
+        ..AST::default()
+      }),
+      "argument_list"|"initializer_list" => children.append(&mut extract_arguments(&child, code)),
+      "new"|"new_declarator" => (),
+      _ => children.push(AST {
+        kind: Kind::Unhandled(format!("extract_new_expression: {}", child.to_sexp())),
+        range: child.byte_range(),
+        ..AST::default()
+      }),
+    }
+  }
+
+  children
+}
+
 fn extract_update_expression(node: &Node, code: &str) -> Vec<AST> {
   let mut children = vec![];
 
@@ -449,7 +861,7 @@ fn extract_update_expression(node: &Node, code: &str) -> Vec<AST> {
       "("|")"|"{"|"}"|";" => (),
       x if is_modify_operator(x) => (),
       x if is_primitive_type(x) => (),
-      "new" => (),
+      "new_expression" => children.append(&mut extract_new_expression(&child, code)),
       "argument_list" => children.append(&mut extract_arguments(&child, code)),
       "type_identifier"|"struct_specifier"
         |"function_declarator" => children.push(AST {
@@ -475,16 +887,27 @@ fn extract_call_expression(node: &Node, code: &str) -> Vec<AST> {
     let child = node.child(idx).unwrap();
     let range = child.byte_range();
     match child.kind() {
-      "identifier"|"qualified_identifier" => children.push(AST {
-        name: code[range.start..range.end].to_string(),
-        kind: Kind::Reference(Reference::Call),
-        range,
-        ..AST::default()
-      } ),
+      "identifier"|"qualified_identifier" => {
+        let name = &code[range.start..range.end];
+        if let Some(instantiated) = get_factory_function_type(name) {
+          children.push(AST {
+            name: instantiated.to_string(),
+            kind: Kind::Reference(Reference::Instantiate),
+            range: range.clone(),
+            ..AST::default()
+          });
+        }
+        children.push(AST {
+          name: name.to_string(),
+          kind: Kind::Reference(Reference::Call),
+          range,
+          ..AST::default()
+        })
+      }
       "template_function" => children.append(&mut extract_call_expression(&child, code)),
       "template_argument_list" => children.append(&mut extract_template_arguments(&child, code)),
       "argument_list" => children.append(&mut extract_arguments(&child, code)),
-      "field_expression" => children.append(&mut extract_field_expression(&child, code)),
+      "field_expression" => children.append(&mut extract_member_call(&child, code)),
       "("|")"|"{"|"}"|";" => (),
       _ => children.push(AST {
         kind: Kind::Unhandled(format!("extract_call_expression: {}", child.to_sexp())),
@@ -497,6 +920,44 @@ fn extract_call_expression(node: &Node, code: &str) -> Vec<AST> {
   children
 }
 
+/// Type created by `std::make_unique<T>` or `std::make_shared<T>`
+fn get_factory_function_type(name: &str) -> Option<&str> {
+  let (function, arguments) = name.split_once('<')?;
+  let is_factory = matches!(function.rsplit("::").next(), Some("make_unique"|"make_shared"));
+  let instantiated = arguments.rsplit_once('>')?.0.trim();
+  is_factory.then_some(instantiated)
+}
+
+/// Calling a method (`object.method()` or `object->method()`). The object
+/// the method is called on is kept as dependency of the call.
+fn extract_member_call(node: &Node, code: &str) -> Vec<AST> {
+  let mut children = extract_field_expression(node, code);
+
+  if let Some(method) = node.child_by_field_name("field") {
+    let range = method.byte_range();
+    let receiver = node.child_by_field_name("argument")
+      .filter(|object| object.kind() == "identifier")
+      .map(|object| {
+        let range = object.byte_range();
+        AST {
+          name: code[range.start..range.end].to_string(),
+          kind: Kind::Reference(Reference::Read),
+          range,
+          ..AST::default()
+        }
+      });
+    children.push(AST {
+      name: code[range.start..range.end].to_string(),
+      kind: Kind::Reference(Reference::Call),
+      dependencies: receiver.into_iter().collect(),
+      range,
+      ..AST::default()
+    });
+  }
+
+  children
+}
+
 fn extract_template_type(node: &Node, code: &str) -> Vec<AST> {
   let mut dependencies = vec![];
 
@@ -616,7 +1077,9 @@ fn extract_arguments(node: &Node, code: &str) -> Vec<AST> {
           name: name.to_string(),
           kind: Kind::Variable(Variable {
             is_const: false,
+            storage: StorageClass::default(),
             visibility: "public".to_string(),
+            declarator: Declarator::Value,
           }),
           range,
           ..AST::default()
@@ -624,11 +1087,12 @@ fn extract_arguments(node: &Node, code: &str) -> Vec<AST> {
       }
       "pointer_expression" => children.append(&mut extract_arguments(&child, code) ),
       x if is_statement(x) => children.append(&mut extract_statement(&child, code)),
+      "new_expression" => children.append(&mut extract_new_expression(&child, code)),
       "field_expression" => children.append(&mut extract_field_expression(&child, code)),
       "call_expression" => children.append(&mut extract_call_expression(&child, code)),
       "update_expression" => children.append(&mut extract_update_expression(&child, code)),
       "sizeof_expression" => (),
-      "("|")"|","|"&"|"*" => (),
+      "("|")"|"{"|"}"|","|"&"|"*" => (),
       "this" => (),
       x if is_literal(x) => (),
       _ => children.push(AST {
@@ -646,6 +1110,7 @@ fn extract_param(node: &Node, code: &str) -> Vec<AST> {
   let mut dependencies = vec![];
   let mut children = vec![];
   let mut name = "".to_string();
+  let mut declarator = Declarator::Value;
 
   for idx in 0..node.child_count() as u32 {
     let child = node.child(idx).unwrap();
@@ -654,6 +1119,7 @@ fn extract_param(node: &Node, code: &str) -> Vec<AST> {
       "identifier"|"pointer_declarator"|"reference_declarator"
         |"array_declarator" => {
         name = get_variable_name(&child, code);
+        declarator = get_declarator(&child, node, code);
       }
       x if is_primitive_type(x) => (),
       "type_identifier"|"struct_specifier"|"qualified_identifier"
@@ -679,7 +1145,9 @@ fn extract_param(node: &Node, code: &str) -> Vec<AST> {
       name: name.to_string(),
       kind: Kind::Variable(Variable {
         is_const: false,
+        storage: StorageClass::default(),
         visibility: "public".to_string(),
+        declarator,
       }),
       children,
       dependencies,
@@ -733,7 +1201,9 @@ fn parse_enum_variant(node: &Node, code: &str, namespace: &str) -> Vec<AST> {
           name: variant_name,
           kind: Kind::Variable(Variable {
             is_const: true,
+            storage: StorageClass::default(),
             visibility: "public".to_string(),
+            declarator: Declarator::Value,
           }),
           range: range.clone(),
           ..AST::default()
@@ -742,7 +1212,9 @@ fn parse_enum_variant(node: &Node, code: &str, namespace: &str) -> Vec<AST> {
           name: qualified_name,
           kind: Kind::Variable(Variable {
             is_const: true,
+            storage: StorageClass::default(),
             visibility: "public".to_string(),
+            declarator: Declarator::Value,
           }),
           range: range,
           ..AST::default()
@@ -772,8 +1244,9 @@ fn parse_struct(node: &Node, code: &str) -> AST {
         let range = child.byte_range();
         name = &code[range.start..range.end];
       }
-      "typedef"|"struct"|"field_declaration_list"
-        |"struct_specifier"|";" => (),
+      "field_declaration_list" => children.append(&mut extract_class_fields(&child, code)),
+      "struct_specifier" => children.append(&mut parse_struct(&child, code).children),
+      "typedef"|"struct"|";" => (),
       _ => children.push(AST {
         kind: Kind::Unhandled(format!("parse_struct: {}", child.to_sexp())),
         range: child.byte_range(),
@@ -883,7 +1356,7 @@ fn get_function_name(node: &Node, code: &str) -> (String, Option<String>) {
         namespace = Some(code[range.start..range.end].to_string());
       },
       "template_type"|"function_declarator"|"qualified_identifier"
-        |"pointer_declarator" => {
+        |"pointer_declarator"|"reference_declarator" => {
         return get_function_name(&child, code)
       },
       _ => (),
@@ -895,21 +1368,37 @@ fn get_function_name(node: &Node, code: &str) -> (String, Option<String>) {
   (name, namespace)
 }
 
-fn check_is_const(node: &Node, code: &str) -> bool {
-  for idx in 0..node.child_count() as u32 {
-    let child = node.child(idx).unwrap();
-    let range = child.byte_range();
-    match child.kind() {
-      "type_qualifier" => {
-        return &code[range.start..range.end] == "constexpr";
-      },
-      "declaration" => {
-        return check_is_const(&child, code);
-      }
-      _ => (),
-    }
+/// Constants are declared `constexpr` or `const`. For pointers `const T*`
+/// only protects the data the pointer points to, the pointer itself is
+/// constant if it is `constexpr` or a const pointer (`T* const`).
+fn check_is_const(node: &Node, declarator: &Node, code: &str) -> bool {
+  if declarator.kind() == "pointer_declarator" {
+    has_specifier(node, code, "type_qualifier", &["constexpr"]) || has_specifier(declarator, code, "type_qualifier", &["const"])
+  } else {
+    has_specifier(node, code, "type_qualifier", &["constexpr", "const"])
   }
-  false
+}
+
+/// Storage class specifiers (`thread_local` variables have static storage
+/// duration as well)
+fn get_storage_class(node: &Node, code: &str) -> StorageClass {
+  StorageClass {
+    is_static: has_specifier(node, code, "storage_class_specifier", &["static", "thread_local"]),
+    is_extern: has_specifier(node, code, "storage_class_specifier", &["extern"]),
+  }
+}
+
+/// Specifiers are part of the declaration (not of its `init_declarator`)
+fn has_specifier(node: &Node, code: &str, kind: &str, specifiers: &[&str]) -> bool {
+  let declaration = match node.kind() {
+    "init_declarator" => node.parent().unwrap(),
+    _ => *node,
+  };
+  (0..declaration.child_count() as u32).any(|idx| {
+    let child = declaration.child(idx).unwrap();
+    let range = child.byte_range();
+    child.kind() == kind && specifiers.contains(&&code[range.start..range.end])
+  })
 }
 
 fn is_literal(kind: &str) -> bool {
@@ -953,7 +1442,7 @@ fn is_modify_operator(kind: &str) -> bool {
 fn is_update_expression(kind: &str) -> bool {
   match kind {
     "update_expression"|"assignment_expression"
-      |"delete_expression"|"new_expression" => true,
+      |"delete_expression" => true,
     _ => false
   }
 }
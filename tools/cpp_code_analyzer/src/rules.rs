@@ -0,0 +1,168 @@
+use std::fmt::Display;
+
+/// The styleguide is embedded at build time, so that `explain` always
+/// matches the rules the analyzer was built with
+const STYLEGUIDE: &str = include_str!("../../../draft/cpp_styleguide.adoc");
+pub const STYLEGUIDE_URL: &str = "https://kober-systems.github.io/guidelines/draft/cpp_styleguide.html";
+
+/// Requirement level of a rule as defined in RFC 2119
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Requirement {
+  Must,
+  MustNot,
+  Should,
+  ShouldNot,
+}
+
+impl Display for Requirement {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    use Requirement::*;
+
+    match &self {
+      Must => write!(f, "MUST"),
+      MustNot => write!(f, "MUST NOT"),
+      Should => write!(f, "SHOULD"),
+      ShouldNot => write!(f, "SHOULD NOT"),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Rule {
+  pub id: &'static str,
+  pub requirement: Requirement,
+  /// Anchor of the section in the styleguide that defines the rule
+  pub anchor: Option<&'static str>,
+}
+
+impl Rule {
+  pub fn url(&self) -> Option<String> {
+    self.anchor.map(|anchor| format!("{STYLEGUIDE_URL}#{anchor}"))
+  }
+}
+
+const fn rule(id: &'static str, requirement: Requirement, anchor: &'static str) -> Rule {
+  Rule { id, requirement, anchor: Some(anchor) }
+}
+
+use Requirement::*;
+
+/// All rules that are checked. Findings of the analyzer itself (e.g.
+/// unhandled syntax or broken lint instructions) are not part of the
+/// styleguide and therefore have no rule.
+pub const RULES: &[Rule] = &[
+  rule("E_MODULES_ABSTRACT_CLASSES_ONLY_PUBLIC_METHODS", Must, "modules_abstract_classes"),
+  rule("E_MODULES_ABSTRACT_CLASSES_NO_ATTRS", MustNot, "modules_abstract_classes"),
+  rule("E_MODULES_ABSTRACT_CLASSES_NO_PRIVATE_METHODS", MustNot, "modules_abstract_classes"),
+  rule("E_MODULES_ABSTRACT_CLASSES_NO_CONSTRUCTOR", MustNot, "modules_abstract_classes"),
+  rule("E_MODULES_ABSTRACT_CLASSES_NAME_PREFIX", Must, "modules_abstract_classes"),
+  rule("E_MODULES_ABSTRACT_CLASSES_PREFIX_ON_CONCRETE_CLASS", Must, "modules_abstract_classes"),
+  rule("E_MODULES_ABSTRACT_CLASSES_CONCRETE_TYPE_IN_SIGNATURE", Must, "modules_abstract_classes"),
+  rule("E_MODULES_ABSTRACT_CLASSES_REVEALS_IMPLEMENTATION", Should, "modules_abstract_classes"),
+  rule("E_MODULES_ABSTRACT_CLASSES_FRIEND", MustNot, "modules_abstract_classes"),
+  rule("E_CPP_ABSTRACT_CLASS_MISSING_DEFAULT_DESTRUCTOR", Should, "modules_abstract_classes"),
+  rule("E_CPP_ABSTRACT_CLASS_METHOD_NOT_VIRTUAL", Must, "modules_abstract_classes"),
+  rule("E_CPP_ABSTRACT_CLASS_METHOD_MISSING_VIRTUAL_ENDING", Must, "modules_abstract_classes"),
+  rule("E_CPP_ABSTRACT_CLASS_INCLUDES_NON_ABSTRACT_HEADER", Must, "modules_abstract_classes"),
+  rule("E_CPP_ABSTRACT_CLASS_HAS_IMPLEMENTATION_FILE", Must, "modules_abstract_classes"),
+  rule("E_MODULES_DERIVE_FROM_ABSTRACT_CLASSES", Should, "modules_derive_from_abstract_classes"),
+  rule("E_MODULES_DERIVE_FROM_ABSTRACT_CLASSES_INSTANTIATED_OUTSIDE_MAIN", Should, "modules_derive_from_abstract_classes"),
+  rule("E_MODULES_DERIVE_FROM_ABSTRACT_CLASSES_CONCRETE_DEPENDENCY", Should, "modules_derive_from_abstract_classes"),
+  rule("E_CPP_DERIVED_CLASS_METHOD_IS_VIRTUAL", MustNot, "modules_derive_from_abstract_classes"),
+  rule("E_CPP_DERIVED_CLASS_METHOD_HAS_VIRTUAL_ENDING", ShouldNot, "modules_derive_from_abstract_classes"),
+  rule("E_CPP_DERIVES_ALWAYS_PUBLIC", Must, "modules_derive_from_abstract_classes"),
+  rule("E_CPP_DERIVES_ALWAYS_FROM_ABSTRACT_INTERFACES", Must, "modules_derive_from_abstract_classes"),
+  rule("E_MODULES_NO_INIT_FUNCTION", ShouldNot, "modules_no_init_function"),
+  rule("E_CPP_UNNECESSARY_INCLUDE", MustNot, "modules_avoid_unnecessary_headers"),
+  rule("E_CPP_INCLUDE_ONLY_NEEDED_BY_IMPLEMENTATION", Must, "modules_avoid_unnecessary_headers"),
+  rule("E_CPP_MISSING_DIRECT_INCLUDE", Should, "modules_avoid_unnecessary_headers"),
+  rule("E_MODULES_DERIVED_CLASSES_ALL_ATTRS_PRIVATE", Should, "modules_derived_classes_all_attrs_private"),
+  rule("E_MODULES_DERIVED_CLASSES_ONLY_INTERFACE_PUBLIC", Should, "modules_derived_classes_all_attrs_private"),
+  rule("E_MODULES_DERIVED_CLASSES_FRIEND", Should, "modules_derived_classes_all_attrs_private"),
+  rule("E_MODULES_DERIVE_FROM_ABSTRACT_CLASSES_EXTRA_METHOD_USAGE", Should, "modules_derived_classes_all_attrs_private"),
+  rule("E_MODULES_AVOID_GETTER_SETTER", Should, "modules_avoid_getter_setter"),
+  rule("E_MODULES_AVOID_GETTER_SETTER_UNUSED", Should, "modules_avoid_getter_setter"),
+  rule("E_DATA_STRUCTURES_RETURNED_BY_REFERENCE", MustNot, "data_structures_by_value"),
+  rule("E_DATA_STRUCTURES_PASSED_BY_REFERENCE", MustNot, "data_structures_by_value"),
+  rule("E_STRUCTS_ENUMS_DEFINED_IN_ABSTRACT_CLASSES", Should, "structs_enums_in_abstract_classes"),
+  rule("E_STRUCTS_ENUMS_STRUCT_HAS_METHOD", Must, "modules_abstract_classes"),
+  rule("E_STRUCTS_ENUMS_STRUCT_NON_POD_MEMBER", Must, "modules_abstract_classes"),
+  rule("E_GLOBAL_VARIABLES_DECLARATION", MustNot, "global_variables"),
+  rule("E_GLOBAL_VARIABLES_USAGE", MustNot, "global_variables"),
+  rule("E_GLOBAL_VARIABLES_STATIC_ATTRIBUTE", MustNot, "global_variables"),
+  rule("E_GLOBAL_VARIABLES_STATIC_LOCAL", MustNot, "global_variables"),
+  rule("E_GLOBAL_VARIABLES_SINGLETON", MustNot, "global_variables"),
+  rule("E_GLOBAL_VARIABLES_MACRO_CONSTANT", Should, "global_variables"),
+  rule("E_BIT_ROT_CONDITIONAL_COMPILATION", ShouldNot, "bit_rot_conditional_compilation"),
+  rule("E_GENERAL_UTF8_ENCODING", Must, "general_utf8_encoding"),
+  rule("E_GENERAL_SPACES_INSTEAD_OF_TABS", Must, "general_spaces_instead_of_tabs"),
+  // formatting conventions of the analyzer without a section in the
+  // styleguide
+  Rule { id: "E_GENERAL_MIXED_LINE_ENDINGS", requirement: Should, anchor: None },
+  Rule { id: "E_GENERAL_MISSING_FINAL_NEWLINE", requirement: Should, anchor: None },
+];
+
+/// IDs of findings about the analysis itself. They are not part of the
+/// styleguide but can be configured like the rules.
+pub const ANALYZER_FINDINGS: &[&str] = &[
+  "E_PARSER_UNHANDLED",
+  "E_LINT_INSTRUCTION_NOT_PARSEABLE",
+  "E_LINT_INSTRUCTION_UNUSED",
+  "E_LINT_INSTRUCTION_MISSING_REASON",
+  "E_LINT_INSTRUCTION_EXPIRED",
+];
+
+pub fn find_rule(id: &str) -> Option<&'static Rule> {
+  RULES.iter().find(|rule| rule.id == id)
+}
+
+/// Requirement text and rationale of a rule. Accepts a rule ID or the
+/// anchor of a styleguide section.
+pub fn explain(rule_or_anchor: &str) -> Option<String> {
+  let rules: Vec<&Rule> = match find_rule(rule_or_anchor) {
+    Some(rule) => vec![rule],
+    None => RULES.iter().filter(|rule| rule.anchor == Some(rule_or_anchor)).collect(),
+  };
+  let anchor = rules.first()?.anchor;
+
+  let mut explanation = String::new();
+  for rule in rules.iter() {
+    explanation.push_str(&format!("{} ({})\n", rule.id, rule.requirement));
+  }
+  match anchor {
+    Some(anchor) => {
+      explanation.push('\n');
+      explanation.push_str(get_styleguide_section(anchor)?.trim_end());
+      explanation.push_str(&format!("\n\nSee {STYLEGUIDE_URL}#{anchor}\n"));
+    }
+    None => explanation.push_str("\nThis rule is not described in the styleguide.\n"),
+  }
+
+  Some(explanation)
+}
+
+/// Text of the section (including its subsections such as "Why?") that
+/// follows the `[[anchor]]`
+fn get_styleguide_section(anchor: &str) -> Option<&'static str> {
+  let marker = format!("[[{anchor}]]\n");
+  let start = STYLEGUIDE.find(&marker)? + marker.len();
+
+  let mut end = STYLEGUIDE.len();
+  let mut offset = start;
+  for (idx, line) in STYLEGUIDE[start..].split_inclusive('\n').enumerate() {
+    if idx > 0 && (line.starts_with("[[") || is_section_heading(line)) {
+      end = offset;
+      break;
+    }
+    offset += line.len();
+  }
+
+  Some(&STYLEGUIDE[start..end])
+}
+
+/// Rules are defined on level 4 (`====`), so every heading up to this level
+/// starts a new rule
+fn is_section_heading(line: &str) -> bool {
+  let level = line.chars().take_while(|c| *c == '=').count();
+  (1..=4).contains(&level) && line[level..].starts_with(' ')
+}
@@ -269,6 +269,8 @@ fn extract_node(input: &AST, code: &str, base: GraphData) -> GraphData {
       base
     },
     Kind::Unhandled(_element) => base,
+    Kind::Conditional => base,
+    Kind::Macro(_) => base,
     _ => todo!()
   }
 }
@@ -283,7 +285,7 @@ fn extract_references(input: &AST, from: &str, code: &str, base: GraphData) -> G
       }
       base.connections.push(Connection {
         kind: match r {
-          Reference::Read|Reference::Write|Reference::Call => ConnectionType::Usage,
+          Reference::Read|Reference::Write|Reference::Call|Reference::Instantiate => ConnectionType::Usage,
           Reference::TypeRead => ConnectionType::Composition,
           r => todo!("{:?}", r),
         },
@@ -300,7 +302,7 @@ fn extract_references(input: &AST, from: &str, code: &str, base: GraphData) -> G
       base
     },
     Kind::Unhandled(_element) => base,
-    Kind::Variable(_) => base,
+    Kind::Variable(_)|Kind::Type|Kind::Conditional|Kind::Friend => base,
     Kind::LintError(_) => base,
     _ => todo!("{:?}", input.kind)
   }
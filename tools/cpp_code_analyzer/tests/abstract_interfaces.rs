@@ -1,4 +1,5 @@
-use cpp_code_analyzer::analyze_cpp;
+use cpp_code_analyzer::{analyze_cpp, analyze_cpp_files, checker, parser};
+use cpp_code_analyzer::ast::LintLabel;
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -60,6 +61,61 @@ private:
     ]);
 }
 
+#[test]
+fn prevent_non_public_methods_in_abstract_classes() {
+    let code = r#"
+class AbstractMyClass {
+public:
+    virtual ~AbstractMyClass() = default;
+
+    virtual void foo() = 0;
+protected:
+    virtual void bar() = 0;
+private:
+    virtual void baz() = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Abstract class `AbstractMyClass` must not define protected methods ('bar')",
+      "Abstract class `AbstractMyClass` must not define private methods ('baz')",
+    ]);
+}
+
+#[test]
+fn prevent_constructors_in_abstract_classes() {
+    let code = r#"
+class AbstractMyClass {
+public:
+    AbstractMyClass(int x);
+    virtual ~AbstractMyClass() = default;
+
+    virtual void foo() = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Abstract class `AbstractMyClass` must not define a constructor",
+    ]);
+}
+
+#[test]
+fn allow_deleted_and_defaulted_special_members_in_abstract_classes() {
+    let code = r#"
+class AbstractMyClass {
+public:
+    AbstractMyClass(const AbstractMyClass&) = delete;
+    AbstractMyClass& operator=(const AbstractMyClass&) = delete;
+    AbstractMyClass(AbstractMyClass&&) = default;
+    virtual ~AbstractMyClass() = default;
+
+    virtual void foo() = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, Vec::<String>::new());
+}
+
 #[test]
 fn make_sure_all_methods_are_virtual_in_abstract_classes() {
     let code = r#"
@@ -79,6 +135,38 @@ public:
     ]);
 }
 
+#[test]
+fn concrete_classes_must_not_be_named_abstract() {
+    let code = r#"
+class AbstractCounter {
+public:
+    void increment();
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "method 'void increment();' in abstract class 'AbstractCounter' must be virtual",
+      "Abstract class 'AbstractCounter': missing `= 0;` for method 'void increment();'",
+      "Class 'AbstractCounter' is named like an abstract class but has concrete members ('increment'). Either make it abstract or remove the `Abstract` prefix",
+      "Abstract class 'AbstractCounter' should provide a default destructor.",
+    ]);
+}
+
+#[test]
+fn classes_with_only_pure_virtual_methods_must_be_named_abstract() {
+    let code = r#"
+class MyInterface {
+public:
+    virtual ~MyInterface() = default;
+    virtual void foo() = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Class 'MyInterface' only declares pure virtual methods and is therefore an abstract class. Its name must follow the schema `Abstract<name>` ('AbstractMyInterface')",
+    ]);
+}
+
 #[test]
 fn should_not_permit_init_function() {
     let code = r#"
@@ -107,3 +195,266 @@ public:
       "Abstract class 'AbstractMyClass' should provide a default destructor."
     ]);
 }
+
+#[test]
+fn abstract_classes_may_include_abstract_and_standard_headers() {
+    let code = r#"
+#include <cstdint>
+#include <stdint.h>
+#include "AbstractHandle.h"
+
+class AbstractMyClass {
+public:
+    virtual ~AbstractMyClass() = default;
+    virtual uint8_t foo(AbstractHandle* handle) = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn abstract_classes_must_not_include_platform_headers() {
+    let code = r#"
+#include <Arduino.h>
+#include "modbus_library.h"
+
+class AbstractMyClass {
+public:
+    virtual ~AbstractMyClass() = default;
+    virtual void foo() = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Abstract class 'AbstractMyClass' must only include headers of abstract classes or the standard library (not allowed <Arduino.h>)",
+      "Abstract class 'AbstractMyClass' must only include headers of abstract classes or the standard library (not allowed \"modbus_library.h\")",
+    ]);
+}
+
+#[test]
+fn abstract_classes_must_not_include_headers_of_concrete_classes() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractMyClass.h", r#"
+#include "AbstractHandle.h"
+#include "MyHandle.h"
+
+class AbstractMyClass {
+public:
+    virtual ~AbstractMyClass() = default;
+    virtual void foo(AbstractHandle* handle) = 0;
+};
+"#),
+      ("src/AbstractHandle.h", r#"
+class AbstractHandle {
+public:
+    virtual ~AbstractHandle() = default;
+};
+"#),
+      ("src/MyHandle.h", r#"
+#include "AbstractHandle.h"
+
+class MyHandle: public AbstractHandle {
+public:
+    MyHandle();
+};
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Abstract class 'AbstractMyClass' must only include headers of abstract classes or the standard library (not allowed \"MyHandle.h\")",
+    ]);
+}
+
+#[test]
+fn include_errors_point_to_the_include_directive() {
+    let code = r#"
+#include <Arduino.h>
+
+class AbstractMyClass {
+public:
+    virtual ~AbstractMyClass() = default;
+    virtual void foo() = 0;
+};
+"#;
+    let ast = parser::parse_cpp_chunc("src/AbstractMyClass.h", code);
+    let errors = checker::check_global_codechunk(vec![ast]);
+    assert_eq!(errors.len(), 1);
+    let include = code.find("#include").unwrap();
+    assert_eq!(errors[0].range, include..include + "#include <Arduino.h>".len());
+}
+
+#[test]
+fn abstract_classes_must_not_have_an_implementation_file() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractMyClass.h", r#"
+class AbstractMyClass {
+public:
+    virtual ~AbstractMyClass() = default;
+    virtual void foo() = 0;
+};
+"#),
+      ("src/my_class.cpp", r#"
+#include "AbstractMyClass.h"
+
+void AbstractMyClass::foo() {
+}
+
+void AbstractMyClass::bar() {
+}
+"#),
+      ("src/AbstractOther.cpp", r#"
+int helper() {
+  return 42;
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Abstract class 'AbstractMyClass' must consist only of a header file. It is not allowed to have a `.cpp` file.",
+      "Abstract class 'AbstractOther' must consist only of a header file. It is not allowed to have a `.cpp` file.",
+    ]);
+}
+
+#[test]
+fn implementation_files_of_abstract_classes_point_to_the_declaration() {
+    let header = r#"
+class AbstractOther {
+public:
+    virtual ~AbstractOther() = default;
+    virtual void foo() = 0;
+};
+"#;
+    let source = r#"
+#include "AbstractOther.h"
+
+int helper() {
+  return 42;
+}
+"#;
+    let ast = vec![
+      parser::parse_cpp_chunc("src/AbstractOther.h", header),
+      parser::parse_cpp_chunc("src/AbstractOther.cpp", source),
+    ];
+    let errors = checker::check_global_codechunk(ast);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].file_path, "src/AbstractOther.cpp");
+    let include = source.find("#include").unwrap();
+    assert_eq!(errors[0].range, include..include + "#include \"AbstractOther.h\"".len());
+    let declaration = header.find("class").unwrap();
+    assert_eq!(errors[0].secondary_labels, [LintLabel {
+      message: "abstract class 'AbstractOther' is declared here".to_string(),
+      range: declaration..header.find("};").unwrap() + 1,
+      file_path: "src/AbstractOther.h".to_string(),
+    }]);
+}
+
+#[test]
+fn abstract_classes_must_only_use_abstract_types_in_signatures() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractLightShow.h", r#"
+#include "AbstractLed.h"
+#include "Led.h"
+
+class AbstractLightShow {
+public:
+    virtual ~AbstractLightShow() = default;
+    virtual void add(AbstractLed& led) = 0;
+    virtual void remove(Led& led) = 0;
+    virtual Led* first() = 0;
+};
+"#),
+      ("src/AbstractLed.h", r#"
+class AbstractLed {
+public:
+    virtual ~AbstractLed() = default;
+};
+"#),
+      ("src/Led.h", r#"
+#include "AbstractLed.h"
+
+class Led: public AbstractLed {
+};
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Method 'remove' of abstract class 'AbstractLightShow' must not depend on concrete class 'Led'. Use its abstract interface instead.",
+      "Method 'first' of abstract class 'AbstractLightShow' must not depend on concrete class 'Led'. Use its abstract interface instead.",
+      "Abstract class 'AbstractLightShow' must only include headers of abstract classes or the standard library (not allowed \"Led.h\")",
+    ]);
+}
+
+#[test]
+fn structs_of_abstract_interfaces_must_be_plain_old_data() {
+    let code = r#"
+#include <string>
+
+class AbstractDisplay {
+public:
+    struct pixel {
+      int x;
+      int y;
+      uint8_t color[3];
+    };
+
+    struct settings {
+      std::string name;
+      void reset();
+    };
+
+    typedef struct {
+      int brightness;
+      virtual void apply() = 0;
+    } state;
+
+    virtual ~AbstractDisplay() = default;
+    virtual void draw(pixel p) = 0;
+    virtual void configure(settings s, state st) = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Structure 'settings' of an abstract interface must be plain old data, but member 'name' is not",
+      "Structure 'settings' of an abstract interface must be plain old data and must not define method 'reset'",
+      "Structure 'state' of an abstract interface must be plain old data and must not define method 'apply'",
+    ]);
+}
+
+#[test]
+fn abstract_classes_must_not_reveal_implementation_details() {
+    let code = r#"
+class AbstractLightShowController {
+public:
+    virtual ~AbstractLightShowController() = default;
+    virtual void send_modbus_request(int request) = 0;
+    virtual void readI2CRegister(int reg) = 0;
+    virtual void configure(GPIOPin pin) = 0;
+    virtual void spin() = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "'send_modbus_request' of abstract class 'AbstractLightShowController' reveals implementation details ('modbus'). Names of abstract interfaces should have a high-level view of the task.",
+      "'readI2CRegister' of abstract class 'AbstractLightShowController' reveals implementation details ('i2c'). Names of abstract interfaces should have a high-level view of the task.",
+      "'GPIOPin' of abstract class 'AbstractLightShowController' reveals implementation details ('gpio'). Names of abstract interfaces should have a high-level view of the task.",
+    ]);
+}
+
+#[test]
+fn forbidden_terms_are_configurable() {
+    let code = r#"
+class AbstractLightShowController {
+public:
+    virtual ~AbstractLightShowController() = default;
+    virtual void send_modbus_request(int request) = 0;
+    virtual void start_ws2812_animation() = 0;
+};
+"#;
+    let config = checker::Config {
+      forbidden_terms: vec!["ws2812".to_string()],
+      ..checker::Config::default()
+    };
+    let errors = checker::check_global_codechunk_with_config(vec![parser::parse_cpp_chunc("", code)], &config);
+    let errors: Vec<_> = errors.into_iter().map(|err| format!("{}", err.kind)).collect();
+    assert_eq!(errors, [
+      "'start_ws2812_animation' of abstract class 'AbstractLightShowController' reveals implementation details ('ws2812'). Names of abstract interfaces should have a high-level view of the task.",
+    ]);
+}
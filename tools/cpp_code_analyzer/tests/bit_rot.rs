@@ -0,0 +1,83 @@
+use cpp_code_analyzer::analyze_cpp;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn allow_conditional_compilation_on_file_level() {
+    let code = r#"
+#ifndef CONTROLLER_H
+#define CONTROLLER_H
+
+#ifdef ARDUINO
+const int led_pin = 13;
+#else
+const int led_pin = 4;
+#endif
+
+int main(void) {
+  return led_pin;
+}
+
+#endif
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn avoid_conditional_compilation_inside_of_classes_and_functions() {
+    let code = r#"
+class AbstractController {
+public:
+    virtual ~AbstractController() = default;
+    virtual void run() = 0;
+};
+
+class Controller : public AbstractController {
+public:
+    void run() {
+#ifdef DEBUG
+        log();
+#endif
+    }
+
+private:
+#if defined(ESP32)
+    int m_pin = 4;
+#elif defined(ARDUINO)
+    int m_pin = 13;
+#else
+    int m_pin = 5;
+#endif
+    void log() {}
+};
+
+int main(void) {
+#if ESP32
+  Controller controller;
+#endif
+  return 0;
+}
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Conditional compilation '#ifdef DEBUG' should not be used inside of 'run'. Write platform agnostic code or switch whole files instead.",
+      "Conditional compilation '#if defined(ESP32)' should not be used inside of 'Controller'. Write platform agnostic code or switch whole files instead.",
+      "Conditional compilation '#if ESP32' should not be used inside of 'main'. Write platform agnostic code or switch whole files instead.",
+    ]);
+}
+
+#[test]
+fn avoid_conditional_compilation_inside_of_structs() {
+    let code = r#"
+struct config {
+  int speed;
+#ifdef DEBUG
+  int log_level;
+#endif
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Conditional compilation '#ifdef DEBUG' should not be used inside of 'config'. Write platform agnostic code or switch whole files instead.",
+    ]);
+}
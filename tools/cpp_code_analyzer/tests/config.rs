@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use cpp_code_analyzer::ast::Severity;
+use cpp_code_analyzer::checker::{self, PathConfig, RuleSetting};
+use cpp_code_analyzer::{config, parser};
+use pretty_assertions::assert_eq;
+
+fn check_with_config(files: &[(&str, &str)], config: &checker::Config) -> Vec<(String, Option<Severity>)> {
+    let ast = files.iter().map(|(name, input)| parser::parse_cpp_chunc(name, input)).collect();
+    checker::check_global_codechunk_with_config(ast, config).into_iter()
+      .map(|err| (format!("{}", err.kind), config.get_severity(&err)))
+      .collect()
+}
+
+#[test]
+fn configure_abstract_prefix_and_entry_points() {
+    let config = checker::Config {
+      abstract_prefix: "I".to_string(),
+      entry_points: vec!["app_main".to_string()],
+      ..checker::Config::default()
+    };
+    let errors = check_with_config(&[("src/app.cpp", r#"
+class ISensor {
+public:
+    virtual ~ISensor() = default;
+    virtual int measure() = 0;
+};
+
+class Sensor: public ISensor {
+public:
+    int measure();
+};
+
+int counter = 0;
+
+int app_main() {
+  return counter;
+}
+"#)], &config);
+    assert_eq!(errors, Vec::<(String, Option<Severity>)>::new());
+}
+
+#[test]
+fn rule_settings_of_subdirectories_win() {
+    let config = checker::Config {
+      paths: vec![
+        PathConfig {
+          rules: HashMap::from([
+            ("E_GLOBAL_VARIABLES_MACRO_CONSTANT".to_string(), RuleSetting::Severity(Severity::Error)),
+            ("E_GLOBAL_VARIABLES_DECLARATION".to_string(), RuleSetting::Severity(Severity::Warning)),
+          ]),
+          ..PathConfig::default()
+        },
+        PathConfig {
+          directory: PathBuf::from("firmware"),
+          depth: 1,
+          rules: HashMap::from([
+            ("E_GLOBAL_VARIABLES_MACRO_CONSTANT".to_string(), RuleSetting::Off),
+            ("E_GLOBAL_VARIABLES_DECLARATION".to_string(), RuleSetting::On),
+          ]),
+          ..PathConfig::default()
+        },
+      ],
+      ..checker::Config::default()
+    };
+    let errors = check_with_config(&[
+      ("firmware/Led.h", "#define LED_PIN 13\nint led_state = 0;\n"),
+      ("desktop/Window.h", "#define WIDTH 640\nint window_state = 0;\n"),
+    ], &config);
+    assert_eq!(errors, [
+      ("It's not allowed to create global variables ('led_state'). Global variables create invisible coupling.".to_string(), Some(Severity::Error)),
+      ("It's not allowed to create global variables ('window_state'). Global variables create invisible coupling.".to_string(), Some(Severity::Warning)),
+      ("Macro 'WIDTH' should be replaced by a typed 'constexpr' constant".to_string(), Some(Severity::Error)),
+    ]);
+}
+
+#[test]
+fn load_configuration_files_of_project_and_subdirectories() {
+    let root = std::env::temp_dir().join(format!("cpp_analyzer_config_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("repo/firmware/vendor")).unwrap();
+    fs::write(root.join("repo/.cpp_analyzer.toml"), r#"
+abstract_prefix = "I"
+entry_points = ["app_main"]
+exclude = ["firmware/vendor/**"]
+
+[rules]
+E_MODULES_NO_INIT_FUNCTION = "error"
+"#).unwrap();
+    fs::write(root.join("repo/firmware/.cpp_analyzer.toml"), r#"
+[rules]
+E_GLOBAL_VARIABLES_MACRO_CONSTANT = "off"
+"#).unwrap();
+
+    let config = config::load_config(&root.join("repo/firmware")).unwrap();
+    assert_eq!(config.abstract_prefix, "I");
+    assert_eq!(config.entry_points, ["app_main"]);
+    assert_eq!(config.paths.len(), 2);
+    assert_eq!(config.paths[0].glob_base, PathBuf::from("firmware"));
+    assert_eq!(config.paths[1].rules, HashMap::from([("E_GLOBAL_VARIABLES_MACRO_CONSTANT".to_string(), RuleSetting::Off)]));
+    assert!(!config.is_included(&root.join("repo/firmware/vendor/sdk.h").to_string_lossy()));
+    assert!(config.is_included(&root.join("repo/firmware/Led.h").to_string_lossy()));
+
+    let config = config::load_config(&root.join("repo")).unwrap();
+    assert_eq!(config.paths.len(), 2);
+    assert_eq!(config.paths[1].rules, HashMap::from([("E_GLOBAL_VARIABLES_MACRO_CONSTANT".to_string(), RuleSetting::Off)]));
+
+    fs::write(root.join("repo/firmware/.cpp_analyzer.toml"), "abstract_prefix = \"I\"\n").unwrap();
+    assert!(config::load_config(&root.join("repo")).is_err());
+    fs::write(root.join("repo/firmware/.cpp_analyzer.toml"), "[rules]\nE_NO_SUCH_RULE = \"off\"\n").unwrap();
+    assert_eq!(config::load_config(&root.join("repo")).err().map(|err| err.ends_with("unknown rule 'E_NO_SUCH_RULE'")), Some(true));
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn rule_settings_of_nested_configuration_files_win_regardless_of_their_order() {
+    let root = std::env::temp_dir().join(format!("cpp_analyzer_config_order_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("repo/firmware")).unwrap();
+    fs::write(root.join("repo/.cpp_analyzer.toml"), "[rules]\nE_GLOBAL_VARIABLES_DECLARATION = \"error\"\n").unwrap();
+    fs::write(root.join("repo/firmware/.cpp_analyzer.toml"), "[rules]\nE_GLOBAL_VARIABLES_DECLARATION = \"warning\"\n").unwrap();
+
+    let mut config = config::load_config(&root.join("repo/firmware")).unwrap();
+    config.paths.reverse();
+    let file = root.join("repo/firmware/Led.h");
+    let errors = check_with_config(&[(&file.to_string_lossy(), "int led_state = 0;\n")], &config);
+    assert_eq!(errors, [
+      ("It's not allowed to create global variables ('led_state'). Global variables create invisible coupling.".to_string(), Some(Severity::Warning)),
+    ]);
+
+    fs::remove_dir_all(&root).unwrap();
+}
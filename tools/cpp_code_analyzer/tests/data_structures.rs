@@ -0,0 +1,236 @@
+use cpp_code_analyzer::{analyze_cpp, analyze_cpp_files};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn data_structures_passed_by_value_happy_path() {
+    let code = r#"
+class AbstractController {
+public:
+    virtual ~AbstractController() = default;
+
+    typedef struct {
+      int speed;
+    } config;
+
+    virtual config get_config() = 0;
+    virtual void set_config(config cfg) = 0;
+    virtual void apply(const config& cfg) = 0;
+    virtual AbstractController* get_parent() = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn data_structures_must_not_be_returned_by_reference() {
+    let code = r#"
+class AbstractController {
+public:
+    virtual ~AbstractController() = default;
+
+    typedef struct {
+      int speed;
+    } config;
+
+    virtual config& get_config() = 0;
+    virtual const config* peek_config() = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Method 'get_config' of class 'AbstractController' must return data structures and attributes by value and not by reference or pointer",
+      "Method 'peek_config' of class 'AbstractController' must return data structures and attributes by value and not by reference or pointer",
+    ]);
+}
+
+#[test]
+fn members_must_not_be_returned_by_reference() {
+    let code = r#"
+class Controller: public AbstractController {
+public:
+    Controller();
+    int& counter() {
+      return m_counter;
+    }
+
+private:
+    int m_counter = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Method 'counter' of class 'Controller' must return data structures and attributes by value and not by reference or pointer",
+      "Getter/setter 'counter' of class 'Controller' is not used outside of the class. Only define getters and setters when there is a concrete need.",
+    ]);
+}
+
+#[test]
+fn members_must_not_be_returned_by_reference_from_out_of_line_definitions() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractController.h", r#"
+class AbstractController {
+public:
+    virtual ~AbstractController() = default;
+    virtual void run() = 0;
+};
+"#),
+      ("src/Controller.h", r#"
+#include "AbstractController.h"
+
+// lint: ignore E_MODULES_DERIVED_CLASSES_ONLY_INTERFACE_PUBLIC reason: testing returned references
+class Controller: public AbstractController {
+public:
+    Controller();
+    void run();
+    int& counter();
+    int& next(int& value);
+
+private:
+    int m_counter = 0;
+};
+"#),
+      ("src/Controller.cpp", r#"
+#include "Controller.h"
+
+void Controller::run() {
+}
+
+int& Controller::counter() {
+  return m_counter;
+}
+
+int& Controller::next(int& value) {
+  value += m_counter;
+  return value;
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Method 'counter' of class 'Controller' must return data structures and attributes by value and not by reference or pointer",
+      "Getter/setter 'counter' of class 'Controller' is not used outside of the class. Only define getters and setters when there is a concrete need.",
+    ]);
+}
+
+#[test]
+fn data_structures_must_not_be_passed_by_non_const_reference() {
+    let code = r#"
+enum class mode {
+  fast,
+  slow,
+};
+
+class AbstractController {
+public:
+    virtual ~AbstractController() = default;
+
+    virtual void read_mode(mode& m) = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Method 'read_mode' of class 'AbstractController' must not take the data structure 'm' by non-const reference",
+      "'mode' belongs to the context of abstract class 'AbstractController' and should be defined inside of the class instead of the global namespace",
+    ]);
+}
+
+#[test]
+fn data_structures_should_be_defined_in_abstract_classes() {
+    let code = r#"
+enum class controller_result {
+  ok = 0,
+  busy = -1,
+};
+
+typedef struct {
+  int speed;
+} controller_lightshow_pattern;
+
+class AbstractLightShowController {
+public:
+    virtual ~AbstractLightShowController() = default;
+
+    virtual controller_result start() = 0;
+    virtual controller_result lightshow_pattern_set(controller_lightshow_pattern pattern) = 0;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "'controller_result' belongs to the context of abstract class 'AbstractLightShowController' and should be defined inside of the class instead of the global namespace",
+      "'controller_lightshow_pattern' belongs to the context of abstract class 'AbstractLightShowController' and should be defined inside of the class instead of the global namespace",
+    ]);
+}
+
+#[test]
+fn data_structures_shared_by_abstract_classes_may_be_global() {
+    let errors = analyze_cpp_files(&[
+      ("src/types.h", r#"
+typedef struct {
+  int x;
+  int y;
+} point;
+"#),
+      ("src/AbstractDisplay.h", r#"
+#include "types.h"
+
+class AbstractDisplay {
+public:
+    virtual ~AbstractDisplay() = default;
+    virtual void draw(point p) = 0;
+};
+"#),
+      ("src/AbstractTouch.h", r#"
+#include "types.h"
+
+class AbstractTouch {
+public:
+    virtual ~AbstractTouch() = default;
+    virtual point last_touch() = 0;
+};
+"#),
+    ]);
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn shared_headers_of_abstract_classes_must_only_contain_plain_old_data() {
+    let errors = analyze_cpp_files(&[
+      ("src/types.h", r#"
+#include <string>
+
+typedef struct {
+  int x;
+  int y;
+} point;
+
+struct label {
+  std::string text;
+};
+"#),
+      ("src/AbstractDisplay.h", r#"
+#include "types.h"
+
+class AbstractDisplay {
+public:
+    virtual ~AbstractDisplay() = default;
+    virtual void draw(point p) = 0;
+};
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Abstract class 'AbstractDisplay' must only include headers of abstract classes or the standard library (not allowed \"types.h\")",
+      "'point' belongs to the context of abstract class 'AbstractDisplay' and should be defined inside of the class instead of the global namespace",
+    ]);
+}
+
+#[test]
+fn local_data_structures_are_allowed_in_functions() {
+    let code = r#"
+int main() {
+  struct Point { int x; int y; } p = {1, 2};
+  return p.x;
+}
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, Vec::<String>::new());
+}
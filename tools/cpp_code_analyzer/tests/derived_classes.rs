@@ -1,4 +1,4 @@
-use cpp_code_analyzer::analyze_cpp;
+use cpp_code_analyzer::{analyze_cpp, analyze_cpp_files, checker, parser};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -116,3 +116,290 @@ public:
     assert_eq!(errors, Vec::<String>::new());
 }
 
+
+const ABSTRACT_INTERFACES: &str = r#"
+class AbstractBase {
+public:
+    virtual ~AbstractBase() = default;
+    virtual void foo() = 0;
+};
+
+class AbstractMyInterface: public AbstractBase {
+public:
+    virtual ~AbstractMyInterface() = default;
+    virtual void bar() = 0;
+};
+"#;
+
+#[test]
+fn only_interface_methods_may_be_public() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractMyInterface.h", ABSTRACT_INTERFACES),
+      ("src/MyClass.h", r#"
+#include "AbstractMyInterface.h"
+
+class MyClass: public AbstractMyInterface {
+public:
+    MyClass();
+    ~MyClass();
+    void foo();
+    void bar();
+    void baz();
+
+private:
+    void helper();
+};
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Derived class 'MyClass' should only make the methods of its abstract interface public ('baz' is not part of the interface)",
+    ]);
+}
+
+#[test]
+fn can_explicitly_allow_public_methods() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractMyInterface.h", ABSTRACT_INTERFACES),
+      ("src/MyClass.h", r#"
+#include "AbstractMyInterface.h"
+
+// lint: ignore E_MODULES_DERIVED_CLASSES_ONLY_INTERFACE_PUBLIC reason: only used in testing scenarios
+class MyClass: public AbstractMyInterface {
+public:
+    MyClass();
+    void foo();
+    void bar();
+    void baz();
+};
+"#),
+    ]);
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn public_attributes_and_methods_are_suppressed_separately() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractMyInterface.h", ABSTRACT_INTERFACES),
+      ("src/MyClass.h", r#"
+#include "AbstractMyInterface.h"
+
+// lint: ignore E_MODULES_DERIVED_CLASSES_ALL_ATTRS_PRIVATE reason: only used in testing scenarios
+class MyClass: public AbstractMyInterface {
+public:
+    MyClass();
+    void foo();
+    void bar();
+    void baz();
+
+    int my_variable = 0;
+};
+"#),
+    ]);
+    assert_eq!(errors, vec![
+      "Derived class 'MyClass' should only make the methods of its abstract interface public ('baz' is not part of the interface)",
+    ]);
+}
+
+#[test]
+fn clients_should_only_use_the_abstract_interface() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractMyInterface.h", ABSTRACT_INTERFACES),
+      ("src/MyClass.h", r#"
+#include "AbstractMyInterface.h"
+
+// lint: ignore E_MODULES_DERIVED_CLASSES_ONLY_INTERFACE_PUBLIC reason: baz is needed for the test
+class MyClass: public AbstractMyInterface {
+public:
+    MyClass();
+    void foo();
+    void bar();
+    void baz();
+};
+"#),
+      ("src/main.cpp", r#"
+#include "AbstractMyInterface.h"
+#include "MyClass.h"
+
+void run(MyClass* concrete, AbstractMyInterface* interface) {
+  MyClass local;
+
+  concrete->foo();
+  concrete->baz();
+  local.baz();
+  interface->bar();
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Concrete class 'MyClass' should only be instantiated in 'main'/'setup' or a factory and not in 'run'. Depend on its abstract interface instead.",
+      "Method 'baz' is not part of the abstract interface of 'MyClass'. Use the abstract interface or extend it instead of depending on the concrete class",
+      "Method 'baz' is not part of the abstract interface of 'MyClass'. Use the abstract interface or extend it instead of depending on the concrete class",
+    ]);
+}
+
+const LED_CLASSES: &str = r#"
+class AbstractLed {
+public:
+    virtual ~AbstractLed() = default;
+    virtual void on() = 0;
+};
+
+class Led: public AbstractLed {
+public:
+    Led(int pin);
+    void on();
+};
+"#;
+
+#[test]
+fn concrete_classes_are_instantiated_in_main_only() {
+    let errors = analyze_cpp_files(&[
+      ("src/Led.h", LED_CLASSES),
+      ("src/main.cpp", r#"
+#include <memory>
+#include "Led.h"
+
+void setup() {
+  Led led(4);
+  AbstractLed* other = new Led(5);
+  auto unique = std::make_unique<Led>(6);
+}
+
+void loop() {}
+"#),
+      ("src/show.cpp", r#"
+#include <memory>
+#include "Led.h"
+
+void show(AbstractLed* injected) {
+  Led led(4);
+  AbstractLed* other = new Led(5);
+  std::unique_ptr<AbstractLed> unique = std::make_unique<Led>(6);
+  injected->on();
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Concrete class 'Led' should only be instantiated in 'main'/'setup' or a factory and not in 'show'. Depend on its abstract interface instead.",
+      "Concrete class 'Led' should only be instantiated in 'main'/'setup' or a factory and not in 'show'. Depend on its abstract interface instead.",
+      "Concrete class 'Led' should only be instantiated in 'main'/'setup' or a factory and not in 'show'. Depend on its abstract interface instead.",
+    ]);
+}
+
+#[test]
+fn new_expressions_instantiate_concrete_classes() {
+    let errors = analyze_cpp_files(&[
+      ("src/Led.h", LED_CLASSES),
+      ("src/lights.cpp", r#"
+#include "Led.h"
+
+AbstractLed* first() {
+  Led* led = new Led(4);
+  return led;
+}
+
+AbstractLed* second() {
+  return new Led(5);
+}
+
+void replace(AbstractLed* led) {
+  led = new Led{6};
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Concrete class 'Led' should only be instantiated in 'main'/'setup' or a factory and not in 'first'. Depend on its abstract interface instead.",
+      "Concrete class 'Led' should only be instantiated in 'main'/'setup' or a factory and not in 'second'. Depend on its abstract interface instead.",
+      "Concrete class 'Led' should only be instantiated in 'main'/'setup' or a factory and not in 'replace'. Depend on its abstract interface instead.",
+    ]);
+}
+
+#[test]
+fn concrete_classes_may_be_instantiated_in_configured_factories() {
+    let ast = vec![
+      parser::parse_cpp_chunc("src/Led.h", LED_CLASSES),
+      parser::parse_cpp_chunc("src/factory/LedFactory.cpp", r#"
+#include "Led.h"
+
+AbstractLed* create_led(int pin) {
+  return new Led(pin);
+}
+"#),
+    ];
+    let config = checker::Config {
+      factory_files: vec!["factory/LedFactory.cpp".to_string()],
+      ..checker::Config::default()
+    };
+    let errors = checker::check_global_codechunk_with_config(ast, &config);
+    assert_eq!(errors, vec![]);
+}
+
+#[test]
+fn derived_classes_depend_on_abstract_interfaces() {
+    let errors = analyze_cpp_files(&[
+      ("src/Led.h", LED_CLASSES),
+      ("src/Timer.h", r#"
+class Timer {
+public:
+    Timer();
+};
+"#),
+      ("src/AbstractLightShow.h", r#"
+class AbstractLightShow {
+public:
+    virtual ~AbstractLightShow() = default;
+    virtual void start() = 0;
+};
+"#),
+      ("src/LightShow.h", r#"
+#include "AbstractLightShow.h"
+#include "Led.h"
+#include "Timer.h"
+
+class LightShow: public AbstractLightShow {
+public:
+    LightShow(Led& first, AbstractLed* second);
+    LightShow(const LightShow& other);
+    void start();
+
+private:
+    Led* m_led;
+    Timer m_timer;
+};
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Class 'Timer' should be derived from abstract interface",
+      "Class 'LightShow' depends on concrete class 'Led' ('first'). Use its abstract interface 'AbstractLed' instead.",
+      "Class 'LightShow' depends on concrete class 'Led' ('m_led'). Use its abstract interface 'AbstractLed' instead.",
+      "Class 'LightShow' depends on concrete class 'Timer' ('m_timer'). Use an abstract interface instead.",
+    ]);
+}
+
+#[test]
+fn friends_bypass_the_interface() {
+    let code = r#"
+class AbstractSensor {
+public:
+    friend class SensorSpy;
+    virtual ~AbstractSensor() = default;
+    virtual int read() = 0;
+};
+
+class Sensor: public AbstractSensor {
+public:
+    int read();
+
+private:
+    friend class SensorTest;
+    friend void dump(const Sensor& sensor);
+    int m_value;
+};
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Abstract class 'AbstractSensor' must not declare 'SensorSpy' as friend",
+      "Friend 'SensorTest' of class 'Sensor' gets access to its private attributes. Use the public interface instead.",
+      "Friend 'dump' of class 'Sensor' gets access to its private attributes. Use the public interface instead.",
+    ]);
+}
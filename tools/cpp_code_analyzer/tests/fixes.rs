@@ -14,9 +14,11 @@ fn apply_change_derive_class() {
       kind: LintErrorTypes::DeriveFromAbstractInterface("MyClass".to_string()),
       range: Range { start: 0, end: 30 },
       file_path: "MyClass.h".to_string(),
+      secondary_labels: vec![],
+      notes: vec![],
     },
     affected_lint_errors: vec![],
-  }], sources);
+  }], sources, "Abstract");
 
   assert_eq!(sources, HashMap::from([
     ("MyClass.h".to_string(), DERIVED_CLASS.to_string()),
@@ -24,6 +26,29 @@ fn apply_change_derive_class() {
   ]));
 }
 
+#[test]
+fn derive_from_interface_with_configured_prefix() {
+  let mut sources: HashMap<String, String> = HashMap::default();
+  sources.insert("MyClass.h".to_string(), NOT_DERIVED_CLASS.to_string());
+
+  let sources = apply_fixes(vec![Fix {
+    instruction: FixInstruction::CreateAbstractClass("MyClass".to_string()),
+    main_lint_err: LintError {
+      kind: LintErrorTypes::DeriveFromAbstractInterface("MyClass".to_string()),
+      range: Range { start: 0, end: 30 },
+      file_path: "MyClass.h".to_string(),
+      secondary_labels: vec![],
+      notes: vec![],
+    },
+    affected_lint_errors: vec![],
+  }], sources, "I");
+
+  assert_eq!(sources, HashMap::from([
+    ("MyClass.h".to_string(), DERIVED_CLASS.replace("AbstractMyClass", "IMyClass")),
+    ("IMyClass.h".to_string(), ABSTRACT_INTERFACE.replace("AbstractMyClass", "IMyClass")),
+  ]));
+}
+
 const NOT_DERIVED_CLASS: &str = r"
 class MyClass {
 public:
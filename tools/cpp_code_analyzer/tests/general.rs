@@ -0,0 +1,46 @@
+use cpp_code_analyzer::{analyze_cpp, analyze_cpp_bytes};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn source_files_must_be_utf8_encoded() {
+    let code = b"// Gr\xfc\xdfe\nint main(void) {\n  return 0;\n}\n";
+    let errors = analyze_cpp_bytes(code);
+    assert_eq!(errors, [
+      "Source files must be encoded in UTF-8 (invalid byte at offset 5)",
+    ]);
+}
+
+#[test]
+fn utf8_encoded_source_files_are_allowed() {
+    let errors = analyze_cpp_bytes("// Grüße\nint main(void) {\n  return 0;\n}\n".as_bytes());
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn use_spaces_instead_of_tabs() {
+    let code = "int main(void) {\n\tconst char* text = \"a\\tb\";\n  \treturn 0;\n}\n";
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Line 2 is indented with tabs. Spaces must be used for formatting instead of tabs.",
+      "Line 3 is indented with tabs. Spaces must be used for formatting instead of tabs.",
+    ]);
+}
+
+#[test]
+fn line_endings_must_be_consistent() {
+    let errors = analyze_cpp("int main(void) {\r\n  return 0;\r\n}\r\n");
+    assert_eq!(errors, Vec::<String>::new());
+
+    let errors = analyze_cpp("int main(void) {\r\n  return 0;\n}\n");
+    assert_eq!(errors, [
+      "Line 2 uses a different line ending (CRLF/LF) than the rest of the file",
+    ]);
+}
+
+#[test]
+fn files_should_end_with_newline() {
+    let errors = analyze_cpp("int main(void) {\n  return 0;\n}");
+    assert_eq!(errors, [
+      "File should end with a newline",
+    ]);
+}
@@ -0,0 +1,147 @@
+use cpp_code_analyzer::analyze_cpp_files;
+use pretty_assertions::assert_eq;
+
+const ABSTRACT_SENSOR: &str = r#"
+class AbstractSensor {
+public:
+    virtual ~AbstractSensor() = default;
+    virtual int measure() = 0;
+};
+"#;
+
+#[test]
+fn report_unused_getters_and_setters() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractSensor.h", ABSTRACT_SENSOR),
+      ("src/Sensor.h", r#"
+#include "AbstractSensor.h"
+
+// lint: ignore E_MODULES_DERIVED_CLASSES_ONLY_INTERFACE_PUBLIC reason: testing getters and setters
+class Sensor: public AbstractSensor {
+public:
+    Sensor();
+    int measure();
+    int get_offset();
+    void setGain(int gain);
+    int offset() {
+      return m_offset;
+    }
+    void scale(int value);
+
+private:
+    int m_offset = 0;
+    int m_scale = 1;
+};
+"#),
+      ("src/Sensor.cpp", r#"
+#include "Sensor.h"
+
+void Sensor::scale(int value) {
+  m_scale = value;
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Getter/setter 'get_offset' of class 'Sensor' is not used outside of the class. Only define getters and setters when there is a concrete need.",
+      "Getter/setter 'setGain' of class 'Sensor' is not used outside of the class. Only define getters and setters when there is a concrete need.",
+      "Getter/setter 'offset' of class 'Sensor' is not used outside of the class. Only define getters and setters when there is a concrete need.",
+      "Getter/setter 'scale' of class 'Sensor' is not used outside of the class. Only define getters and setters when there is a concrete need.",
+    ]);
+}
+
+#[test]
+fn suggest_actions_instead_of_used_getters() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractSensor.h", ABSTRACT_SENSOR),
+      ("src/Sensor.h", r#"
+#include "AbstractSensor.h"
+
+// lint: ignore E_MODULES_DERIVED_CLASSES_ONLY_INTERFACE_PUBLIC reason: testing getters and setters
+class Sensor: public AbstractSensor {
+public:
+    Sensor();
+    int measure();
+    int get_offset();
+
+private:
+    int m_offset = 0;
+};
+"#),
+      ("src/main.cpp", r#"
+#include "Sensor.h"
+
+int main() {
+  Sensor sensor;
+  return sensor.get_offset();
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Method 'get_offset' is not part of the abstract interface of 'Sensor'. Use the abstract interface or extend it instead of depending on the concrete class",
+      "Consider replacing getter/setter 'get_offset' of class 'Sensor' with a method that performs a specific action",
+    ]);
+}
+
+#[test]
+fn report_getters_and_setters_of_abstract_interfaces() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractHeater.h", r#"
+class AbstractHeater {
+public:
+    virtual ~AbstractHeater() = default;
+    virtual int get_temperature() = 0;
+    virtual void set_power(int power) = 0;
+};
+"#),
+      ("src/main.cpp", r#"
+#include "AbstractHeater.h"
+
+int report(AbstractHeater* heater) {
+  return heater->get_temperature();
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Consider replacing getter/setter 'get_temperature' of class 'AbstractHeater' with a method that performs a specific action",
+      "Getter/setter 'set_power' of class 'AbstractHeater' is not used outside of the class. Only define getters and setters when there is a concrete need.",
+    ]);
+}
+
+#[test]
+fn calls_of_methods_with_the_same_name_on_other_classes_are_no_usage() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractSensor.h", ABSTRACT_SENSOR),
+      ("src/Sensor.h", r#"
+#include "AbstractSensor.h"
+
+// lint: ignore E_MODULES_DERIVED_CLASSES_ONLY_INTERFACE_PUBLIC reason: testing getters and setters
+class Sensor: public AbstractSensor {
+public:
+    Sensor();
+    int measure();
+    int get_offset();
+
+private:
+    int m_offset = 0;
+};
+"#),
+      ("src/Calibration.h", r#"
+struct Calibration {
+    int offset;
+};
+"#),
+      ("src/main.cpp", r#"
+#include "Calibration.h"
+#include "Sensor.h"
+
+int main() {
+  Sensor sensor;
+  Calibration calibration;
+  return calibration.get_offset() + sensor.measure();
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Getter/setter 'get_offset' of class 'Sensor' is not used outside of the class. Only define getters and setters when there is a concrete need.",
+    ]);
+}
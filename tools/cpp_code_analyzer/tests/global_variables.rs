@@ -1,4 +1,5 @@
-use cpp_code_analyzer::analyze_cpp;
+use cpp_code_analyzer::{analyze_cpp, analyze_cpp_files, checker, parser};
+use cpp_code_analyzer::ast::LintLabel;
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -68,6 +69,19 @@ constexpr my_struct my_constant_global_struct;
     assert_eq!(errors, Vec::<String>::new());
 }
 
+#[test]
+fn pointers_to_constant_data_are_global_variables() {
+    let code = r#"
+const char* name = "x";
+const int limit = 3;
+const char* const label = "y";
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "It's not allowed to create global variables ('name'). Global variables create invisible coupling.",
+    ]);
+}
+
 #[test]
 fn prevent_usage_of_global_variables() {
     let code = r#"
@@ -209,3 +223,191 @@ int MyClass::foo() {
     let errors = analyze_cpp(code);
     assert_eq!(errors, Vec::<String>::new());
 }
+
+const MAIN_FILE: &str = r#"
+#include "worker.h"
+
+int counter = 0;
+int limit = 10;
+
+void setup() {
+  counter = limit;
+}
+
+void loop() {
+  work();
+}
+"#;
+
+const WORKER_HEADER: &str = r#"
+void work();
+"#;
+
+#[test]
+fn prevent_usage_of_globals_of_main_file_in_other_files() {
+    let worker = r#"
+#include "worker.h"
+
+extern int counter;
+
+void work() {
+  counter++;
+  int local = limit;
+}
+"#;
+    let errors = analyze_cpp_files(&[
+      ("src/main.cpp", MAIN_FILE),
+      ("src/worker.h", WORKER_HEADER),
+      ("src/worker.cpp", worker),
+    ]);
+    assert_eq!(errors, [
+      "It's not allowed to use global variables ('counter'). Global variables create invisible coupling.",
+      "It's not allowed to use global variables ('counter'). Global variables create invisible coupling.",
+      "It's not allowed to use global variables ('limit'). Global variables create invisible coupling.",
+    ]);
+}
+
+#[test]
+fn point_to_definition_of_global_used_in_other_file() {
+    let worker = r#"
+#include "worker.h"
+
+extern int counter;
+
+void work() {}
+"#;
+    let ast = vec![
+      parser::parse_cpp_chunc("src/main.cpp", MAIN_FILE),
+      parser::parse_cpp_chunc("src/worker.h", WORKER_HEADER),
+      parser::parse_cpp_chunc("src/worker.cpp", worker),
+    ];
+    let errors = checker::check_global_codechunk(ast);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].file_path, "src/worker.cpp");
+    let definition = MAIN_FILE.find("counter").unwrap();
+    assert_eq!(errors[0].secondary_labels, [LintLabel {
+      message: "'counter' is defined here".to_string(),
+      range: definition..definition + "counter".len(),
+      file_path: "src/main.cpp".to_string(),
+    }]);
+}
+
+#[test]
+fn prevent_hidden_global_state_in_classes() {
+    let code = r#"
+class AbstractRegistry {
+public:
+    virtual ~AbstractRegistry() = default;
+    virtual void count() = 0;
+};
+
+class Registry : public AbstractRegistry {
+public:
+    static Registry& instance();
+    void count() {
+        static int calls = 0;
+        static constexpr int max_calls = 10;
+        static const int min_calls = 1;
+        calls++;
+    }
+
+private:
+    static int s_registered;
+    static constexpr int s_max = 3;
+    static const int s_min = 1;
+    thread_local static int s_current;
+};
+
+Registry& Registry::instance() {
+    static Registry registry;
+    return registry;
+}
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Derived class 'Registry' should only make the methods of its abstract interface public ('instance' is not part of the interface)",
+      "Singleton access 'instance' of class 'Registry' is hidden global state. Global variables create invisible coupling.",
+      "Static variable 'calls' in function 'count' is hidden global state. Global variables create invisible coupling.",
+      "Static attribute 's_registered' of class 'Registry' is hidden global state. Global variables create invisible coupling.",
+      "Static attribute 's_current' of class 'Registry' is hidden global state. Global variables create invisible coupling.",
+      "Static variable 'registry' in function 'instance' is hidden global state. Global variables create invisible coupling.",
+      "Concrete class 'Registry' should only be instantiated in 'main'/'setup' or a factory and not in 'instance'. Depend on its abstract interface instead.",
+    ]);
+}
+
+#[test]
+fn prefer_constexpr_over_macro_constants() {
+    let code = r#"
+#ifndef CONFIG_H
+#define CONFIG_H
+
+#define MAX_SPEED 100
+#define DEVICE_NAME "lamp"
+#define DEBUG
+#define SQUARE(x) ((x) * (x))
+
+#endif
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Macro 'MAX_SPEED' should be replaced by a typed 'constexpr' constant",
+      "Macro 'DEVICE_NAME' should be replaced by a typed 'constexpr' constant",
+    ]);
+}
+
+#[test]
+fn macro_constants_may_refer_to_other_constants() {
+    let code = r#"
+constexpr int base_delay = 10;
+
+#define PIN A0
+#define DELAY (base_delay * 2)
+#define NOW millis()
+#define STEP 20;
+#define ulong unsigned long
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Macro 'PIN' should be replaced by a typed 'constexpr' constant",
+      "Macro 'DELAY' should be replaced by a typed 'constexpr' constant",
+    ]);
+}
+
+#[test]
+fn macro_constants_point_to_the_directive_line() {
+    let code = r#"
+#define LED_PIN 13
+"#;
+    let ast = parser::parse_cpp_chunc("src/led.h", code);
+    let errors = checker::check_global_codechunk(vec![ast]);
+    assert_eq!(errors.len(), 1);
+    let directive = code.find("#define").unwrap();
+    assert_eq!(errors[0].range, directive..directive + "#define LED_PIN 13".len());
+}
+
+#[test]
+fn prevent_macros_accessing_global_variables() {
+    let worker = r#"
+#include "worker.h"
+
+#define COUNT() counter++
+#define RESET(x) do { x = limit; } while (0)
+#define SET_POS(pos) pos.counter = 0
+
+void work() {}
+"#;
+    let ast = vec![
+      parser::parse_cpp_chunc("src/main.cpp", MAIN_FILE),
+      parser::parse_cpp_chunc("src/worker.h", WORKER_HEADER),
+      parser::parse_cpp_chunc("src/worker.cpp", worker),
+    ];
+    let errors = checker::check_global_codechunk(ast);
+    let messages: Vec<_> = errors.iter().map(|err| (format!("{}", err.kind), err.notes.clone())).collect();
+    assert_eq!(messages, [
+      ("It's not allowed to use global variables ('counter'). Global variables create invisible coupling.".to_string(),
+        vec!["the global variable is accessed by macro 'COUNT' and therefore by every expansion of it".to_string()]),
+      ("It's not allowed to use global variables ('limit'). Global variables create invisible coupling.".to_string(),
+        vec!["the global variable is accessed by macro 'RESET' and therefore by every expansion of it".to_string()]),
+    ]);
+    assert_eq!(errors[0].secondary_labels[0].file_path, "src/main.cpp");
+}
@@ -42,6 +42,27 @@ class Derived: public AbstractInterface {
   });
 }
 
+#[test]
+fn mark_interfaces_with_configured_prefix() {
+  let code = r#"
+class IInterface {
+public:
+  virtual ~IInterface() = default;
+  virtual void foo() = 0;
+};
+
+class Derived: public IInterface {
+  Derived() {}
+  void foo() {}
+};
+"#;
+  let ast = vec![parser::parse_cpp_chunc("sample.cpp", code)];
+  let ast = checker::mark_abstract_classes(ast, "I");
+  let g = visualize::ast_to_graph(ast, code);
+  assert_eq!(g.nodes["IInterface"].kind, "A");
+  assert_eq!(g.nodes["Derived"].kind, "C");
+}
+
 #[test]
 fn show_dependencies_on_global_variables() {
   let code = r#"
@@ -290,6 +311,29 @@ class Derived: public AbstractInterface {
   });
 }
 
+#[test]
+fn skip_data_structures_nested_in_classes() {
+  let code = r#"
+class AbstractSensor {
+public:
+  virtual ~AbstractSensor() = default;
+  struct Inner { int v; };
+  virtual void reset() = 0;
+};
+"#;
+  let g = parse_to_graph_with_errors(code);
+  assert_eq!(g, GraphData {
+    nodes: BTreeMap::from([
+      ("AbstractSensor".to_string(), Entity {
+        kind: "A".to_string(),
+        name: "AbstractSensor".to_string(),
+        problematic: vec![],
+      }),
+    ]),
+    connections: vec![],
+  });
+}
+
 fn parse_to_graph(code: &str) -> GraphData {
   let ast = vec![parser::parse_cpp_chunc("sample.cpp", code)];
   let ast = checker::filter_references_in_scope(ast);
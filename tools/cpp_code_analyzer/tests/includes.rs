@@ -0,0 +1,183 @@
+use cpp_code_analyzer::analyze_cpp_files;
+use pretty_assertions::assert_eq;
+
+const ABSTRACT_HANDLE: &str = r#"
+class AbstractHandle {
+public:
+    virtual ~AbstractHandle() = default;
+    virtual void foo() = 0;
+};
+"#;
+
+const ABSTRACT_LOGGER: &str = r#"
+class AbstractLogger {
+public:
+    virtual ~AbstractLogger() = default;
+    virtual void log() = 0;
+};
+"#;
+
+#[test]
+fn necessary_includes_happy_path() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractHandle.h", ABSTRACT_HANDLE),
+      ("src/MyHandle.h", r#"
+#include "AbstractHandle.h"
+
+class MyHandle: public AbstractHandle {
+public:
+    MyHandle();
+    void foo();
+};
+"#),
+      ("src/MyHandle.cpp", r#"
+#include "MyHandle.h"
+
+void MyHandle::foo() {
+}
+"#),
+    ]);
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn report_unused_includes() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractHandle.h", ABSTRACT_HANDLE),
+      ("src/AbstractLogger.h", ABSTRACT_LOGGER),
+      ("src/MyHandle.h", r#"
+#include "AbstractHandle.h"
+#include "AbstractLogger.h"
+
+class MyHandle: public AbstractHandle {
+public:
+    MyHandle();
+    void foo();
+};
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Header \"AbstractLogger.h\" is included but not needed",
+    ]);
+}
+
+#[test]
+fn report_includes_only_needed_by_implementation() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractHandle.h", ABSTRACT_HANDLE),
+      ("src/AbstractLogger.h", ABSTRACT_LOGGER),
+      ("src/MyHandle.h", r#"
+#include "AbstractHandle.h"
+#include "AbstractLogger.h"
+
+class MyHandle: public AbstractHandle {
+public:
+    MyHandle();
+    void foo();
+};
+"#),
+      ("src/MyHandle.cpp", r#"
+#include "MyHandle.h"
+
+void MyHandle::foo() {
+  AbstractLogger *logger = nullptr;
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Header \"AbstractLogger.h\" is only needed by the implementation and must be included in 'src/MyHandle.cpp' instead",
+    ]);
+}
+
+#[test]
+fn report_types_used_without_direct_include() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractHandle.h", ABSTRACT_HANDLE),
+      ("src/AbstractLogger.h", r#"
+#include "AbstractHandle.h"
+
+class AbstractLogger {
+public:
+    virtual ~AbstractLogger() = default;
+    virtual void log(AbstractHandle* handle) = 0;
+};
+"#),
+      ("src/MyLogger.h", r#"
+#include "AbstractLogger.h"
+
+class MyLogger: public AbstractLogger {
+public:
+    MyLogger(AbstractHandle* handle);
+    void log(AbstractHandle* handle);
+};
+"#),
+    ]);
+    assert_eq!(errors, [
+      "'AbstractHandle' is used without including \"AbstractHandle.h\" directly. Include it to make the dependency explicit",
+    ]);
+}
+
+#[test]
+fn members_and_qualified_names_do_not_need_a_direct_include() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractHandle.h", ABSTRACT_HANDLE),
+      ("src/Led.h", r#"
+#include "AbstractHandle.h"
+
+class Led: public AbstractHandle {
+public:
+    void foo();
+};
+"#),
+      ("src/update.h", r#"
+void update();
+"#),
+      ("src/MyHandle.h", r#"
+#include "AbstractHandle.h"
+
+class MyHandle: public AbstractHandle {
+public:
+    MyHandle();
+    void foo();
+};
+"#),
+      ("src/MyHandle.cpp", r#"
+#include "MyHandle.h"
+
+void MyHandle::foo() {
+  hal::Led *status = hal::status_led();
+  hal::Display *display = hal::display();
+  display->update();
+}
+"#),
+    ]);
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn headers_with_macros_are_needed_where_the_macros_are_used() {
+    let errors = analyze_cpp_files(&[
+      ("src/AbstractHandle.h", ABSTRACT_HANDLE),
+      ("src/config.h", "#define LED_PIN 13\n"),
+      ("src/MyHandle.h", r#"
+#include "AbstractHandle.h"
+
+class MyHandle: public AbstractHandle {
+public:
+    MyHandle();
+    void foo();
+};
+"#),
+      ("src/MyHandle.cpp", r#"
+#include "MyHandle.h"
+#include "config.h"
+
+void MyHandle::foo() {
+  digitalWrite(LED_PIN, 1);
+}
+"#),
+    ]);
+    assert_eq!(errors, [
+      "Macro 'LED_PIN' should be replaced by a typed 'constexpr' constant",
+    ]);
+}
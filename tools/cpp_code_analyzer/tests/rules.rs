@@ -0,0 +1,83 @@
+use cpp_code_analyzer::analyze_cpp_errors;
+use cpp_code_analyzer::ast::Severity;
+use cpp_code_analyzer::rules::{explain, Requirement, RULES};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn every_rule_links_to_a_styleguide_section() {
+    for rule in RULES.iter().filter(|rule| rule.anchor.is_some()) {
+      let explanation = explain(rule.id).unwrap_or_else(|| panic!("no section for {}", rule.id));
+      assert!(explanation.contains("===== Why?"), "no rationale for {}", rule.id);
+    }
+}
+
+#[test]
+fn explain_rule_with_requirement_and_rationale() {
+    let explanation = explain("E_MODULES_NO_INIT_FUNCTION").unwrap();
+    assert_eq!(explanation, r#"E_MODULES_NO_INIT_FUNCTION (SHOULD NOT)
+
+==== Derived classes should not have an init function
+Derived classes SHOULD NOT have an `init` function; instead, the class
+SHOULD be initialized directly in the constructor.
+
+===== Why?
+If you have a separate `init` function, there is a difference in
+calling the other functions of a class, depending on whether the class
+has already been initialized or not. To avoid errors, you must catch
+whether the class has already been initialized or not in each of these
+functions. This is error-prone and also requires constant runtime
+computation for the check.
+
+If you perform the initialization in the constructor, an uninitialized
+state cannot occur when using the class.
+
+See https://kober-systems.github.io/guidelines/draft/cpp_styleguide.html#modules_no_init_function
+"#);
+}
+
+#[test]
+fn explain_all_rules_of_a_section() {
+    let explanation = explain("data_structures_by_value").unwrap();
+    assert!(explanation.starts_with("E_DATA_STRUCTURES_RETURNED_BY_REFERENCE (MUST NOT)\nE_DATA_STRUCTURES_PASSED_BY_REFERENCE (MUST NOT)\n\n==== Data structures should be passed by value, not by reference\n"));
+    assert_eq!(explain("unknown_rule"), None);
+}
+
+#[test]
+fn findings_know_their_rule() {
+    let code = r#"
+int counter = 0;
+
+class Controller {
+public:
+    void init();
+};
+"#;
+    let rules: Vec<_> = analyze_cpp_errors("", code).into_iter()
+      .map(|err| err.kind.rule().map(|rule| (rule.id, rule.requirement, rule.anchor)))
+      .collect();
+    assert_eq!(rules, [
+      Some(("E_GLOBAL_VARIABLES_DECLARATION", Requirement::MustNot, Some("global_variables"))),
+      Some(("E_MODULES_NO_INIT_FUNCTION", Requirement::ShouldNot, Some("modules_no_init_function"))),
+      Some(("E_MODULES_DERIVE_FROM_ABSTRACT_CLASSES", Requirement::Should, Some("modules_derive_from_abstract_classes"))),
+    ]);
+}
+
+#[test]
+fn severity_follows_requirement_level() {
+    let code = r#"
+int counter = 0;
+
+class Controller {
+public:
+    void init();
+};
+"#;
+    let severities: Vec<_> = analyze_cpp_errors("", code).into_iter()
+      .map(|err| (err.kind.id(), err.kind.severity()))
+      .collect();
+    assert_eq!(severities, [
+      ("E_GLOBAL_VARIABLES_DECLARATION", Severity::Error),
+      ("E_MODULES_NO_INIT_FUNCTION", Severity::Warning),
+      ("E_MODULES_DERIVE_FROM_ABSTRACT_CLASSES", Severity::Warning),
+    ]);
+}
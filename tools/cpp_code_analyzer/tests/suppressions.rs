@@ -0,0 +1,155 @@
+use cpp_code_analyzer::{analyze_cpp, checker, parser};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn suppress_finding_in_function() {
+    let code = r#"
+int counter = 0;
+
+// lint: ignore E_GLOBAL_VARIABLES_USAGE reason: counter is owned by the legacy driver
+void count() {
+  counter++;
+}
+
+void reset() {
+  counter = 0;
+}
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "It's not allowed to create global variables ('counter'). Global variables create invisible coupling.",
+      "It's not allowed to use global variables ('counter'). Global variables create invisible coupling.",
+    ]);
+}
+
+#[test]
+fn suppress_finding_of_variable() {
+    let code = r#"
+// lint: ignore E_GLOBAL_VARIABLES_DECLARATION reason: required by the vendor SDK
+int sdk_handle = 0;
+int counter = 0;
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "It's not allowed to create global variables ('counter'). Global variables create invisible coupling.",
+    ]);
+}
+
+#[test]
+fn suppress_finding_in_single_line() {
+    let code = r#"
+int counter = 0; // lint: ignore E_GLOBAL_VARIABLES_DECLARATION reason: required by the vendor SDK
+
+void run() {
+  counter++;
+  // lint: ignore E_GLOBAL_VARIABLES_USAGE reason: reset of the vendor SDK
+  counter = 0;
+}
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "It's not allowed to use global variables ('counter'). Global variables create invisible coupling.",
+    ]);
+}
+
+#[test]
+fn suppress_finding_in_whole_file() {
+    let code = r#"
+// lint: ignore-file E_GLOBAL_VARIABLES_USAGE reason: generated code
+int counter = 0;
+
+void count() {
+  counter++;
+}
+
+int value() {
+  return counter;
+}
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "It's not allowed to create global variables ('counter'). Global variables create invisible coupling.",
+    ]);
+}
+
+#[test]
+fn report_unused_and_unjustified_suppressions() {
+    let code = r#"
+// lint: ignore E_GLOBAL_VARIABLES_DECLARATION reason: nothing to suppress here
+int value() {
+  return 0;
+}
+
+/* lint: ignore E_GLOBAL_VARIABLES_DECLARATION */
+int counter = 0;
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "Lint instruction 'E_GLOBAL_VARIABLES_DECLARATION' does not suppress any finding and should be removed",
+      "Lint instruction 'E_GLOBAL_VARIABLES_DECLARATION' is missing a reason. Deviations from the guidelines must be justified.",
+    ]);
+}
+
+#[test]
+fn expired_suppressions_report_findings_again() {
+    let code = r#"
+// lint: ignore E_GLOBAL_VARIABLES_DECLARATION until=2024-03-31 ticket=FW-42 reason: removed with the new driver
+int sdk_handle = 0;
+// lint: ignore E_GLOBAL_VARIABLES_DECLARATION until=2024-04-01 ticket=FW-43 reason: removed with the new driver
+int sdk_state = 0;
+"#;
+    let config = checker::Config {
+      today: Some("2024-04-01".to_string()),
+      ..checker::Config::default()
+    };
+    let errors = checker::check_global_codechunk_with_config(vec![parser::parse_cpp_chunc("", code)], &config);
+    let errors: Vec<_> = errors.into_iter().map(|err| format!("{}", err.kind)).collect();
+    assert_eq!(errors, [
+      "It's not allowed to create global variables ('sdk_handle'). Global variables create invisible coupling.",
+      "Lint instruction 'E_GLOBAL_VARIABLES_DECLARATION' was only valid until 2024-03-31. Fix the suppressed findings or extend the deadline.",
+    ]);
+}
+
+#[test]
+fn suppressions_do_not_expire_without_a_date() {
+    let code = r#"
+// lint: ignore E_GLOBAL_VARIABLES_DECLARATION until=2024-03-31 ticket=FW-42 reason: removed with the new driver
+int sdk_handle = 0;
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn report_all_suppressions() {
+    let ast = vec![
+      parser::parse_cpp_chunc("src/Driver.h", r#"
+// lint: ignore-file E_GENERAL_SPACES_INSTEAD_OF_TABS reason: vendor header
+int sdk_handle = 0; // lint: ignore E_GLOBAL_VARIABLES_DECLARATION until=2024-03-31 ticket=FW-42 reason: removed with the new driver
+"#),
+      parser::parse_cpp_chunc("src/main.cpp", r#"
+int main() {
+  /* lint: ignore E_GLOBAL_VARIABLES_USAGE */
+  return 0;
+}
+"#),
+    ];
+    assert_eq!(checker::report_lint_instructions(&ast), [
+      "src/Driver.h:2 E_GENERAL_SPACES_INSTEAD_OF_TABS reason: vendor header",
+      "src/Driver.h:3 E_GLOBAL_VARIABLES_DECLARATION until=2024-03-31 ticket=FW-42 reason: removed with the new driver",
+      "src/main.cpp:3 E_GLOBAL_VARIABLES_USAGE",
+    ]);
+}
+
+#[test]
+fn reject_malformed_expiry_date() {
+    let code = r#"
+// lint: ignore E_GLOBAL_VARIABLES_DECLARATION until=31.03.2024 reason: removed with the new driver
+int sdk_handle = 0;
+"#;
+    let errors = analyze_cpp(code);
+    assert_eq!(errors, [
+      "It's not allowed to create global variables ('sdk_handle'). Global variables create invisible coupling.",
+      "could not parse lint instruction in comment: // lint: ignore E_GLOBAL_VARIABLES_DECLARATION until=31.03.2024 reason: removed with the new driver",
+    ]);
+}